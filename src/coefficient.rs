@@ -0,0 +1,331 @@
+use crate::crt::{convolve_bigint, convolve_i64, convolve_u64};
+use crate::fft;
+use crate::ntt::{self, ModInt};
+use num::bigint::BigInt;
+use num::complex::Complex;
+use num::rational::Ratio;
+use num::traits::NumAssign;
+use num::{Integer, One, Signed, Zero};
+use std::convert::TryFrom;
+use std::fmt::Debug;
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// At or below this many coefficients in the shorter factor, a schoolbook `i64` product beats
+/// the CRT convolution, which runs several NTTs and recombines the residues in `BigInt`.
+const I64_SCHOOLBOOK_THRESHOLD: usize = 32;
+
+/// A ring the coefficients of a [`Polynomial`](crate::Polynomial) live in.
+///
+/// Each type picks its own multiplication backend through [`Coefficient::convolve`]:
+/// floating point types use the FFT, modular integers the NTT and plain integers the
+/// multi-prime CRT convolution. The default is schoolbook multiplication.
+pub trait Coefficient:
+    Clone
+    + Debug
+    + PartialEq
+    + Zero
+    + One
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Neg<Output = Self>
+    + AddAssign
+    + SubAssign
+    + MulAssign
+{
+    /// Whether arithmetic is exact. Floating point types set this to `false`, which steers
+    /// algorithms whose rounding error grows too quickly onto slower stable paths.
+    const EXACT: bool = true;
+
+    /// Computes the linear convolution of `a` and `b`, which has `a.len() + b.len() - 1`
+    /// entries, or none if either input is empty.
+    fn convolve(a: &[Self], b: &[Self]) -> Vec<Self> {
+        convolve_schoolbook(a, b)
+    }
+}
+
+/// Computes the linear convolution of `a` and `b` term by term, in `O(a.len() b.len())`.
+pub(crate) fn convolve_schoolbook<T: Coefficient>(a: &[T], b: &[T]) -> Vec<T> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+
+    let mut ret = vec![T::zero(); a.len() + b.len() - 1];
+    for (i, x) in a.iter().enumerate() {
+        for (y, r) in b.iter().zip(ret[i..].iter_mut()) {
+            *r += x.clone() * y.clone();
+        }
+    }
+
+    ret
+}
+
+/// A [`Coefficient`] ring in which every nonzero element has a multiplicative inverse, which
+/// is what polynomial division needs.
+pub trait Field: Coefficient + Div<Output = Self> {}
+
+/// Coefficients with a square root, used to start the power series square root.
+pub trait SquareRoot: Sized {
+    /// Returns a square root, or `None` if there is none in this type.
+    fn square_root(&self) -> Option<Self>;
+}
+
+/// Coefficients with a complex conjugate, used to correlate sequences. It is the identity for
+/// real and integer types.
+pub trait Conjugate {
+    fn conj(&self) -> Self;
+}
+
+/// Coefficients with an absolute value, used to compare remainders against a tolerance.
+pub trait Magnitude {
+    /// Returns the absolute value as an `f64`.
+    fn magnitude(&self) -> f64;
+}
+
+impl Coefficient for Complex<f64> {
+    const EXACT: bool = false;
+
+    fn convolve(a: &[Self], b: &[Self]) -> Vec<Self> {
+        fft::convolve(a, b)
+    }
+}
+
+impl Coefficient for Complex<f32> {
+    const EXACT: bool = false;
+
+    fn convolve(a: &[Self], b: &[Self]) -> Vec<Self> {
+        let widen = |x: &[Self]| -> Vec<Complex<f64>> {
+            x.iter().map(|c| Complex::new(c.re as f64, c.im as f64)).collect()
+        };
+
+        fft::convolve(&widen(a), &widen(b))
+            .into_iter()
+            .map(|c| Complex::new(c.re as f32, c.im as f32))
+            .collect()
+    }
+}
+
+impl Coefficient for f64 {
+    const EXACT: bool = false;
+
+    fn convolve(a: &[Self], b: &[Self]) -> Vec<Self> {
+        fft::convolve_real(a, b)
+    }
+}
+
+impl Coefficient for f32 {
+    const EXACT: bool = false;
+
+    fn convolve(a: &[Self], b: &[Self]) -> Vec<Self> {
+        let widen = |x: &[Self]| -> Vec<f64> { x.iter().map(|&c| c as f64).collect() };
+
+        fft::convolve_real(&widen(a), &widen(b)).into_iter().map(|c| c as f32).collect()
+    }
+}
+
+/// Short factors are multiplied term by term in `i128`, long ones with the CRT convolution.
+///
+/// Panics if a coefficient of the product does not fit in `i64`.
+impl Coefficient for i64 {
+    fn convolve(a: &[Self], b: &[Self]) -> Vec<Self> {
+        let wide = if a.len().min(b.len()) <= I64_SCHOOLBOOK_THRESHOLD {
+            convolve_i64_schoolbook(a, b).unwrap_or_else(|| convolve_i64(a, b))
+        } else {
+            convolve_i64(a, b)
+        };
+
+        wide.into_iter()
+            .map(|x| i64::try_from(x).expect("polynomial coefficient overflows i64"))
+            .collect()
+    }
+}
+
+/// Computes the linear convolution of `a` and `b` term by term in `i128`, or `None` if a
+/// partial sum overflows.
+fn convolve_i64_schoolbook(a: &[i64], b: &[i64]) -> Option<Vec<i128>> {
+    if a.is_empty() || b.is_empty() {
+        return Some(Vec::new());
+    }
+
+    let mut ret = vec![0i128; a.len() + b.len() - 1];
+    for (i, &x) in a.iter().enumerate() {
+        for (&y, r) in b.iter().zip(ret[i..].iter_mut()) {
+            *r = r.checked_add(x as i128 * y as i128)?;
+        }
+    }
+
+    Some(ret)
+}
+
+impl Coefficient for BigInt {
+    fn convolve(a: &[Self], b: &[Self]) -> Vec<Self> {
+        convolve_bigint(a, b)
+    }
+}
+
+impl<const P: u32> Coefficient for ModInt<P> {
+    fn convolve(a: &[Self], b: &[Self]) -> Vec<Self> {
+        let len = (a.len() + b.len()).saturating_sub(1);
+        if len.next_power_of_two().trailing_zeros() <= Self::MAX_LOG {
+            return ntt::convolve(a, b);
+        }
+
+        // `P` has too few factors of two for a direct NTT of this length, so convolve the
+        // representatives exactly and reduce afterwards.
+        let lift = |x: &[Self]| -> Vec<u64> { x.iter().map(|c| c.value() as u64).collect() };
+        convolve_u64(&lift(a), &lift(b))
+            .into_iter()
+            .map(|x| ModInt::new((x % P as u128) as u64))
+            .collect()
+    }
+}
+
+impl<T> Coefficient for Ratio<T> where T: Clone + Debug + Integer + Signed + NumAssign {}
+
+impl Field for Complex<f64> {}
+impl Field for Complex<f32> {}
+impl Field for f64 {}
+impl Field for f32 {}
+impl<const P: u32> Field for ModInt<P> {}
+impl<T> Field for Ratio<T> where T: Clone + Debug + Integer + Signed + NumAssign {}
+
+impl SquareRoot for Complex<f64> {
+    fn square_root(&self) -> Option<Self> {
+        Some(self.sqrt())
+    }
+}
+
+impl SquareRoot for Complex<f32> {
+    fn square_root(&self) -> Option<Self> {
+        Some(self.sqrt())
+    }
+}
+
+impl SquareRoot for f64 {
+    fn square_root(&self) -> Option<Self> {
+        Some(self.sqrt()).filter(|x| !x.is_nan())
+    }
+}
+
+impl SquareRoot for f32 {
+    fn square_root(&self) -> Option<Self> {
+        Some(self.sqrt()).filter(|x| !x.is_nan())
+    }
+}
+
+impl<const P: u32> SquareRoot for ModInt<P> {
+    fn square_root(&self) -> Option<Self> {
+        self.sqrt()
+    }
+}
+
+impl Magnitude for Complex<f64> {
+    fn magnitude(&self) -> f64 {
+        self.norm()
+    }
+}
+
+impl Magnitude for Complex<f32> {
+    fn magnitude(&self) -> f64 {
+        self.norm() as f64
+    }
+}
+
+impl Magnitude for f64 {
+    fn magnitude(&self) -> f64 {
+        self.abs()
+    }
+}
+
+impl Magnitude for f32 {
+    fn magnitude(&self) -> f64 {
+        self.abs() as f64
+    }
+}
+
+impl Conjugate for Complex<f64> {
+    fn conj(&self) -> Self {
+        Complex::conj(self)
+    }
+}
+
+impl Conjugate for Complex<f32> {
+    fn conj(&self) -> Self {
+        Complex::conj(self)
+    }
+}
+
+impl Conjugate for f64 {
+    fn conj(&self) -> Self {
+        *self
+    }
+}
+
+impl Conjugate for f32 {
+    fn conj(&self) -> Self {
+        *self
+    }
+}
+
+impl Conjugate for i64 {
+    fn conj(&self) -> Self {
+        *self
+    }
+}
+
+impl Conjugate for BigInt {
+    fn conj(&self) -> Self {
+        self.clone()
+    }
+}
+
+impl<const P: u32> Conjugate for ModInt<P> {
+    fn conj(&self) -> Self {
+        *self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mod_int_convolve_around_max_ntt_length() {
+        // `97 - 1 = 2^5 * 3`, so products of up to 32 coefficients take the NTT and longer
+        // ones the CRT fallback.
+        type M = ModInt<97>;
+        for &(n, m) in &[(16, 16), (16, 17), (17, 17), (40, 3)] {
+            let a: Vec<M> = (0..n as u64).map(|i| M::new(i * i + 3)).collect();
+            let b: Vec<M> = (0..m as u64).map(|i| M::new(5 * i + 11)).collect();
+            assert_eq!(M::convolve(&a, &b), convolve_schoolbook(&a, &b), "{} x {}", n, m);
+        }
+        assert!(M::convolve(&[], &[]).is_empty());
+    }
+
+    #[test]
+    fn i64_convolve_around_the_schoolbook_threshold() {
+        let t = I64_SCHOOLBOOK_THRESHOLD;
+        for &(n, m) in &[(1, 1), (2, 2), (t, 200), (t + 1, 200), (150, 90)] {
+            let a: Vec<i64> = (0..n as i64).map(|i| (i * i * 7919) % 100_003 - 50_000).collect();
+            let b: Vec<i64> = (0..m as i64).map(|i| (1 << 30) + i * 31).collect();
+            let expected: Vec<i64> = convolve_i64(&a, &b).into_iter().map(|x| x as i64).collect();
+            assert_eq!(i64::convolve(&a, &b), expected, "{} x {}", n, m);
+        }
+        assert!(i64::convolve(&[], &[1]).is_empty());
+    }
+
+    #[test]
+    fn i64_schoolbook_reports_i128_overflow() {
+        assert_eq!(
+            convolve_i64_schoolbook(&[i64::MIN, 1], &[3]),
+            Some(vec![3 * i64::MIN as i128, 3])
+        );
+        assert_eq!(convolve_i64_schoolbook(&[i64::MIN; 2], &[i64::MIN; 2]), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "polynomial coefficient overflows i64")]
+    fn i64_convolve_panics_on_overflow() {
+        i64::convolve(&[i64::MAX, 1], &[2]);
+    }
+}
@@ -0,0 +1,146 @@
+//! Convolution and correlation of plain slices, for data that is not naturally a
+//! [`Polynomial`](crate::Polynomial).
+//!
+//! Every function works for any [`Coefficient`] type and multiplies with the backend of that
+//! type: the real or complex FFT for floating point numbers, the multi-prime NTT for
+//! integers and the NTT for modular integers. When one input is short the transforms cost
+//! more than they save, and the product is computed directly instead.
+
+use crate::coefficient::{convolve_schoolbook, Coefficient, Conjugate};
+
+/// At or below this many entries in the shorter input, direct convolution beats the
+/// transforms.
+const DIRECT_THRESHOLD: usize = 64;
+
+/// Computes the linear convolution of `a` and `b`, which has `a.len() + b.len() - 1` entries,
+/// or none if either input is empty.
+///
+/// As with [`Polynomial`](crate::Polynomial) multiplication, integer results must fit in `T`.
+pub fn convolve<T: Coefficient>(a: &[T], b: &[T]) -> Vec<T> {
+    if a.len().min(b.len()) <= DIRECT_THRESHOLD {
+        convolve_schoolbook(a, b)
+    } else {
+        T::convolve(a, b)
+    }
+}
+
+/// Computes the cyclic convolution of `a` and `b` with period `n`, whose entry `k` sums
+/// `a[i] * b[j]` over all `i + j` congruent to `k` modulo `n`.
+///
+/// Inputs may be longer or shorter than `n`; the linear convolution is folded onto `n`
+/// entries, so no wrap-around is lost.
+///
+/// Panics if `n` is zero.
+pub fn cyclic_convolve<T: Coefficient>(a: &[T], b: &[T], n: usize) -> Vec<T> {
+    assert!(n > 0, "cyclic convolution needs a positive period");
+
+    let mut ret = vec![T::zero(); n];
+    for (k, c) in convolve(a, b).into_iter().enumerate() {
+        ret[k % n] += c;
+    }
+    ret
+}
+
+/// Computes the cross-correlation of `a` and `b` at every lag where they overlap, which is the
+/// convolution of `a` with `b` reversed and conjugated.
+///
+/// Entry `k` of the result is `sum_i a[i + k - (b.len() - 1)] * conj(b[i])`, so the entry at
+/// index `b.len() - 1` is the correlation at lag zero.
+pub fn correlate<T: Coefficient + Conjugate>(a: &[T], b: &[T]) -> Vec<T> {
+    let reversed: Vec<T> = b.iter().rev().map(Conjugate::conj).collect();
+    convolve(a, &reversed)
+}
+
+/// Computes the correlation of `a` with itself, which has `2 * a.len() - 1` entries centered
+/// on lag zero at index `a.len() - 1`.
+pub fn autocorrelate<T: Coefficient + Conjugate>(a: &[T]) -> Vec<T> {
+    correlate(a, a)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{max_error, random_vec, M};
+    use num::complex::Complex;
+
+    #[test]
+    fn convolve_on_both_sides_of_the_threshold() {
+        let t = DIRECT_THRESHOLD;
+        for &(m, n) in &[(1, 300), (t, 300), (t + 1, 300), (200, 250)] {
+            let (a, b): (Vec<i64>, Vec<i64>) = (random_vec(m, 1), random_vec(n, 2));
+            let mut expected = vec![0; m + n - 1];
+            for (i, x) in a.iter().enumerate() {
+                for (j, y) in b.iter().enumerate() {
+                    expected[i + j] += x * y;
+                }
+            }
+            assert_eq!(convolve(&a, &b), expected, "{} x {}", m, n);
+            assert_eq!(convolve(&b, &a), expected, "{} x {}", n, m);
+
+            let modular = |x: &[i64]| -> Vec<M> { x.iter().map(|&c| M::from(c)).collect() };
+            assert_eq!(convolve(&modular(&a), &modular(&b)), modular(&expected));
+
+            let float = |x: &[i64]| -> Vec<f64> { x.iter().map(|&c| c as f64).collect() };
+            let error = max_error(&convolve(&float(&a), &float(&b)), &float(&expected));
+            assert!(error < 1e-6, "{} x {}: error {}", m, n, error);
+        }
+        assert!(convolve::<i64>(&[], &[1, 2]).is_empty());
+    }
+
+    #[test]
+    fn cyclic_convolve_folds_long_inputs() {
+        for &(m, len_b, n) in &[(10, 7, 4), (3, 2, 8), (100, 90, 37), (5, 5, 1)] {
+            let (a, b): (Vec<i64>, Vec<i64>) = (random_vec(m, 3), random_vec(len_b, 4));
+            let mut expected = vec![0; n];
+            for (i, x) in a.iter().enumerate() {
+                for (j, y) in b.iter().enumerate() {
+                    expected[(i + j) % n] += x * y;
+                }
+            }
+            assert_eq!(cyclic_convolve(&a, &b, n), expected, "{} x {} mod {}", m, len_b, n);
+        }
+        assert_eq!(cyclic_convolve::<i64>(&[], &[], 3), vec![0; 3]);
+    }
+
+    #[test]
+    fn correlate_follows_the_lag_formula() {
+        for &(m, n) in &[(5, 3), (3, 5), (150, 80), (80, 150)] {
+            let a: Vec<Complex<f64>> = random_vec(m, 5);
+            let b: Vec<Complex<f64>> = random_vec(n, 6);
+            let expected: Vec<Complex<f64>> = (0..m + n - 1)
+                .map(|k| {
+                    (0..n)
+                        .filter_map(|i| {
+                            let j = (i + k).checked_sub(n - 1).filter(|&j| j < m)?;
+                            Some(a[j] * b[i].conj())
+                        })
+                        .sum()
+                })
+                .collect();
+            let error = max_error(&correlate(&a, &b), &expected);
+            assert!(error < 1e-9, "{} x {}: error {}", m, n, error);
+        }
+
+        // Lag zero sits at `b.len() - 1`, and shifting `a` right by one moves the peak with it.
+        let b = [Complex::new(1., 0.), Complex::new(0., 1.)];
+        let a = [Complex::default(), b[0], b[1]];
+        let r = correlate(&a, &b);
+        assert_eq!(r[b.len() - 1 + 1], Complex::new(2., 0.));
+    }
+
+    #[test]
+    fn autocorrelate_conjugates_complex_input() {
+        let a = [Complex::new(1., 2.), Complex::new(3., -1.)];
+        let expected = [Complex::new(1., 7.), Complex::new(15., 0.), Complex::new(1., -7.)];
+        assert_eq!(autocorrelate(&a), expected);
+
+        let a: Vec<Complex<f64>> = random_vec(DIRECT_THRESHOLD + 37, 7);
+        let r = autocorrelate(&a);
+        assert_eq!(r.len(), 2 * a.len() - 1);
+        let energy: f64 = a.iter().map(|x| x.norm_sqr()).sum();
+        assert!((r[a.len() - 1] - Complex::new(energy, 0.)).norm() < 1e-9);
+        for k in 0..a.len() {
+            assert!((r[a.len() - 1 + k] - r[a.len() - 1 - k].conj()).norm() < 1e-9, "lag {}", k);
+        }
+    }
+}
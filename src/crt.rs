@@ -0,0 +1,281 @@
+//! Exact integer convolution by running the NTT modulo several primes and recombining the
+//! residues with the Chinese Remainder Theorem.
+//!
+//! The primes are taken from [`NTT_PRIMES`](crate::ntt::NTT_PRIMES), and only as many as the
+//! coefficient bound requires are used. Their product is about `2^239`, which caps the
+//! magnitude of the product coefficients.
+//!
+//! Each prime `p` only supports NTTs up to the largest power of two dividing `p - 1`, so the
+//! primes are ordered by that power and the product length is limited by the last one used:
+//! `2^27` for up to three primes (about 93 bits of coefficient bound), `2^24` for up to six
+//! (about 179 bits) and `2^21` for all eight.
+
+use crate::ntt::{convolve, pow_mod, ModInt};
+use num::bigint::BigInt;
+use num::{Integer, One, ToPrimitive, Zero};
+
+const CRT_PRIMES: [u32; 8] = [
+    3_221_225_473,
+    2_281_701_377,
+    2_013_265_921,
+    469_762_049,
+    167_772_161,
+    754_974_721,
+    998_244_353,
+    1_004_535_809,
+];
+
+/// Computes the linear convolution of `a` and `b` exactly.
+///
+/// Results of up to `2^24` coefficients are always supported. Panics if a coefficient of the
+/// result does not fit in `i128`, in which case use [`convolve_bigint`], or if the result is
+/// longer than the primes needed for it allow, as described there.
+pub fn convolve_i64(a: &[i64], b: &[i64]) -> Vec<i128> {
+    let a: Vec<BigInt> = a.iter().map(|&x| BigInt::from(x)).collect();
+    let b: Vec<BigInt> = b.iter().map(|&x| BigInt::from(x)).collect();
+
+    convolve_bigint(&a, &b)
+        .iter()
+        .map(|x| x.to_i128().expect("convolution coefficient overflows i128"))
+        .collect()
+}
+
+/// Computes the linear convolution of `a` and `b` exactly.
+///
+/// Results of up to `2^24` coefficients are always supported. Panics if a coefficient of the
+/// result does not fit in `u128`, in which case use [`convolve_bigint`], or if the result is
+/// longer than the primes needed for it allow, as described there.
+pub fn convolve_u64(a: &[u64], b: &[u64]) -> Vec<u128> {
+    let a: Vec<BigInt> = a.iter().map(|&x| BigInt::from(x)).collect();
+    let b: Vec<BigInt> = b.iter().map(|&x| BigInt::from(x)).collect();
+
+    convolve_bigint(&a, &b)
+        .iter()
+        .map(|x| x.to_u128().expect("convolution coefficient overflows u128"))
+        .collect()
+}
+
+/// Computes the linear convolution of `a` and `b` exactly.
+///
+/// Panics if the coefficient bound `max|a| * max|b| * min(a.len(), b.len())` exceeds the
+/// capacity of the available primes, or if the result has more than `2^k` coefficients, where
+/// `2^k` is the longest NTT supported by every prime the bound needs. See the
+/// [module documentation](self) for the values of `k`.
+pub fn convolve_bigint(a: &[BigInt], b: &[BigInt]) -> Vec<BigInt> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+
+    let max_bits = |x: &[BigInt]| x.iter().map(BigInt::bits).max().unwrap_or(0);
+    let len_bits = (usize::BITS - a.len().min(b.len()).leading_zeros()) as u64;
+    // One extra bit so that negative results can be told apart from large positive ones.
+    let bound_bits = max_bits(a) + max_bits(b) + len_bits + 1;
+
+    let mut primes = Vec::new();
+    let mut capacity_bits = 0.;
+    for &p in CRT_PRIMES.iter() {
+        if capacity_bits > bound_bits as f64 {
+            break;
+        }
+        primes.push(p);
+        capacity_bits += (p as f64).log2();
+    }
+    assert!(
+        capacity_bits > bound_bits as f64,
+        "convolution coefficients need {} bits, which exceeds the CRT capacity",
+        bound_bits
+    );
+    let len = a.len() + b.len() - 1;
+    let max_log = primes.iter().map(|&p| (p - 1).trailing_zeros()).min().unwrap();
+    assert!(
+        len.next_power_of_two().trailing_zeros() <= max_log,
+        "convolution length {} exceeds 2^{}, the longest NTT supported by the {} primes needed \
+         for {}-bit coefficients",
+        len,
+        max_log,
+        primes.len(),
+        bound_bits
+    );
+
+    let residues: Vec<Vec<u32>> = primes
+        .iter()
+        .map(|&p| {
+            let reduce = |x: &[BigInt]| -> Vec<u32> {
+                x.iter().map(|c| c.mod_floor(&BigInt::from(p)).to_u32().unwrap()).collect()
+            };
+            convolve_mod(p, &reduce(a), &reduce(b))
+        })
+        .collect();
+
+    let garner = Garner::new(&primes);
+    (0..len).map(|i| garner.reconstruct(residues.iter().map(|r| r[i]))).collect()
+}
+
+fn convolve_mod(p: u32, a: &[u32], b: &[u32]) -> Vec<u32> {
+    macro_rules! dispatch {
+        ($($prime:literal),*) => {
+            match p {
+                $(
+                    $prime => {
+                        let a: Vec<ModInt<$prime>> = a.iter().map(|&x| ModInt::from(x)).collect();
+                        let b: Vec<ModInt<$prime>> = b.iter().map(|&x| ModInt::from(x)).collect();
+                        convolve(&a, &b).into_iter().map(ModInt::value).collect()
+                    }
+                )*
+                _ => unreachable!("{} is not a CRT prime", p),
+            }
+        };
+    }
+
+    dispatch!(
+        3_221_225_473,
+        2_281_701_377,
+        2_013_265_921,
+        469_762_049,
+        167_772_161,
+        754_974_721,
+        998_244_353,
+        1_004_535_809
+    )
+}
+
+/// Garner's algorithm for recombining residues modulo pairwise coprime primes into the
+/// unique value in the symmetric range around zero.
+struct Garner {
+    primes: Vec<u64>,
+    /// `inverses[i]` is the inverse of `primes[0] * ... * primes[i - 1]` modulo `primes[i]`.
+    inverses: Vec<u64>,
+    modulus: BigInt,
+}
+
+impl Garner {
+    fn new(primes: &[u32]) -> Self {
+        let primes: Vec<u64> = primes.iter().map(|&p| p as u64).collect();
+        let inverses = primes
+            .iter()
+            .enumerate()
+            .map(|(i, &p)| {
+                let prefix = primes[..i].iter().fold(1, |acc, &q| acc * (q % p) % p);
+                pow_mod(prefix, p - 2, p)
+            })
+            .collect();
+        let modulus = primes.iter().fold(BigInt::one(), |acc, &p| acc * p);
+
+        Garner { primes, inverses, modulus }
+    }
+
+    fn reconstruct(&self, residues: impl Iterator<Item = u32>) -> BigInt {
+        let mut digits: Vec<u64> = Vec::with_capacity(self.primes.len());
+
+        for (i, r) in residues.enumerate() {
+            let p = self.primes[i];
+            // Value of the mixed-radix prefix modulo `p`.
+            let (prefix, _) =
+                digits.iter().zip(self.primes.iter()).fold((0, 1), |(acc, radix), (&d, &q)| {
+                    ((acc + d * radix) % p, radix * (q % p) % p)
+                });
+            let digit = (r as u64 + p - prefix) % p * self.inverses[i] % p;
+            digits.push(digit);
+        }
+
+        let mut value = BigInt::zero();
+        for (&d, &p) in digits.iter().zip(self.primes.iter()).rev() {
+            value = value * p + d;
+        }
+
+        if (&value << 1) > self.modulus {
+            value -= &self.modulus;
+        }
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::random_vec;
+
+    fn naive(a: &[BigInt], b: &[BigInt]) -> Vec<BigInt> {
+        let mut ret = vec![BigInt::zero(); a.len() + b.len() - 1];
+        for (i, x) in a.iter().enumerate() {
+            for (j, y) in b.iter().enumerate() {
+                ret[i + j] += x * y;
+            }
+        }
+        ret
+    }
+
+    /// Returns `len` integers with every bit random.
+    fn random_i64(len: usize, seed: u64) -> Vec<i64> {
+        random_vec::<u64>(len, seed).into_iter().map(|x| x as i64).collect()
+    }
+
+    #[test]
+    fn convolve_i64_matches_naive() {
+        for &(n, m) in &[(1, 1), (3, 17), (100, 61), (257, 300)] {
+            // Large enough to need several primes, small enough for the sums to fit in `i128`.
+            let mut a: Vec<i64> = random_i64(n, 1).into_iter().map(|x| x >> 12).collect();
+            let b = random_i64(m, 2);
+            a[0] = i64::MIN;
+            let big = |x: &[i64]| -> Vec<BigInt> { x.iter().map(|&c| BigInt::from(c)).collect() };
+            let expected: Vec<i128> =
+                naive(&big(&a), &big(&b)).iter().map(|x| x.to_i128().unwrap()).collect();
+            assert_eq!(convolve_i64(&a, &b), expected);
+        }
+    }
+
+    #[test]
+    fn convolve_u64_matches_naive() {
+        let a: Vec<u64> = random_i64(90, 3).into_iter().map(|x| x as u64 >> 4).collect();
+        let b = vec![u64::MAX >> 4; 40];
+        let big = |x: &[u64]| -> Vec<BigInt> { x.iter().map(|&c| BigInt::from(c)).collect() };
+        let expected: Vec<u128> =
+            naive(&big(&a), &big(&b)).iter().map(|x| x.to_u128().unwrap()).collect();
+        assert_eq!(convolve_u64(&a, &b), expected);
+    }
+
+    #[test]
+    fn convolve_bigint_uses_every_prime() {
+        // About 100-bit inputs need all eight primes.
+        let a: Vec<BigInt> = random_i64(50, 4)
+            .into_iter()
+            .map(|x| BigInt::from(x) << 40u32 | BigInt::from(x as u32))
+            .collect();
+        let b: Vec<BigInt> =
+            random_i64(70, 5).into_iter().map(|x| -(BigInt::from(x) << 50u32)).collect();
+        assert_eq!(convolve_bigint(&a, &b), naive(&a, &b));
+    }
+
+    #[test]
+    fn crt_primes_are_ordered_by_ntt_length() {
+        let logs: Vec<u32> = CRT_PRIMES.iter().map(|&p| (p - 1).trailing_zeros()).collect();
+        assert_eq!(logs, [30, 27, 27, 26, 25, 24, 23, 21]);
+    }
+
+    #[test]
+    #[should_panic(expected = "convolution length 2097153 exceeds 2^21")]
+    fn convolve_bigint_rejects_lengths_beyond_the_last_prime() {
+        // 104-bit inputs need all eight primes, which limits the product to 2^21 terms.
+        let mut a = vec![BigInt::zero(); 1 << 21];
+        a[0] = BigInt::one() << 103u32;
+        let b = vec![BigInt::one() << 103u32; 2];
+        convolve_bigint(&a, &b);
+    }
+
+    #[test]
+    fn garner_reconstructs_symmetric_range() {
+        let garner = Garner::new(&CRT_PRIMES[..3]);
+        for value in [
+            BigInt::zero(),
+            BigInt::from(-1),
+            BigInt::from(i64::MAX),
+            -(&garner.modulus >> 1u32),
+            &garner.modulus >> 1u32,
+        ] {
+            let residues = CRT_PRIMES[..3]
+                .iter()
+                .map(|&p| value.mod_floor(&BigInt::from(p)).to_u32().unwrap());
+            assert_eq!(garner.reconstruct(residues), value);
+        }
+    }
+}
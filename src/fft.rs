@@ -0,0 +1,254 @@
+use num::complex::Complex;
+use std::f64::consts::FRAC_PI_4;
+
+mod nd;
+mod plan;
+
+pub use nd::{fft_2d, fft_nd, inverse_fft_2d, inverse_fft_nd};
+pub use plan::{FftPlan, FftPlanner, RealFftPlan};
+
+thread_local! {
+    /// Serves the free functions of this module. Its plans live as long as the thread unless
+    /// [`clear_plan_cache`] is called.
+    static PLANNER: FftPlanner = FftPlanner::new();
+}
+
+/// Evaluates the polynomial with coefficients `input` at the `n`-th roots of unity.
+///
+/// Any length is accepted: lengths whose prime factors are at most 7 use mixed-radix
+/// Cooley-Tukey, with a plain radix-2 kernel for powers of two, and everything else goes
+/// through Bluestein's algorithm. Plans are cached per thread, so repeated calls with the same
+/// length only pay for the transform itself. The cache keeps every length a thread has used,
+/// about 40 bytes per point, until [`clear_plan_cache`] is called.
+pub fn fft(input: &[Complex<f64>]) -> Vec<Complex<f64>> {
+    with_plan(input.len(), |plan| plan.fft(input))
+}
+
+/// Recovers coefficients from the values produced by [`fft`].
+pub fn inverse_fft(input: &[Complex<f64>]) -> Vec<Complex<f64>> {
+    with_plan(input.len(), |plan| plan.inverse_fft(input))
+}
+
+/// Replaces `buf` with its [`fft`], without allocating once the length has been planned on
+/// this thread.
+pub fn fft_in_place(buf: &mut [Complex<f64>]) {
+    with_plan(buf.len(), |plan| plan.fft_in_place(buf));
+}
+
+/// Replaces `buf` with its [`inverse_fft`], without allocating once the length has been
+/// planned on this thread.
+pub fn inverse_fft_in_place(buf: &mut [Complex<f64>]) {
+    with_plan(buf.len(), |plan| plan.inverse_fft_in_place(buf));
+}
+
+/// Writes the [`fft`] of `input` to `output`, which must have the same length.
+pub fn fft_into(input: &[Complex<f64>], output: &mut [Complex<f64>]) {
+    with_plan(input.len(), |plan| plan.fft_into(input, output));
+}
+
+/// Writes the [`inverse_fft`] of `input` to `output`, which must have the same length.
+pub fn inverse_fft_into(input: &[Complex<f64>], output: &mut [Complex<f64>]) {
+    with_plan(input.len(), |plan| plan.inverse_fft_into(input, output));
+}
+
+/// Drops the plans cached on this thread by the free functions of this module, such as
+/// [`fft`] and [`convolve`], releasing their memory once no other reference holds them.
+pub fn clear_plan_cache() {
+    PLANNER.with(FftPlanner::clear);
+}
+
+fn with_plan<R>(len: usize, f: impl FnOnce(&FftPlan) -> R) -> R {
+    PLANNER.with(|planner| f(&planner.plan(len)))
+}
+
+/// Computes the linear convolution of `a` and `b` by pointwise multiplication of their FFTs.
+///
+/// The result has `a.len() + b.len() - 1` coefficients, or none if either input is empty.
+///
+/// Each coefficient is within `f64::EPSILON * log2(n) * |a| * |b|` of the exact value, where
+/// `n` is the transform length and `|.|` the Euclidean norm. The typical error is much
+/// smaller: for 2^19 random integers in `[-1024, 1024)` per input it stays below `1e-6`, so
+/// rounding recovers the exact integer product.
+pub fn convolve(a: &[Complex<f64>], b: &[Complex<f64>]) -> Vec<Complex<f64>> {
+    PLANNER.with(|planner| convolve_with(a, b, planner))
+}
+
+/// Same as [`convolve`], taking the transform from `planner`.
+pub fn convolve_with(
+    a: &[Complex<f64>],
+    b: &[Complex<f64>],
+    planner: &FftPlanner,
+) -> Vec<Complex<f64>> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+
+    let new_size = a.len() + b.len() - 1;
+    let plan = planner.plan(good_size(new_size));
+
+    let mut a = a.to_owned();
+    let mut b = b.to_owned();
+    a.resize_with(plan.len(), Default::default);
+    b.resize_with(plan.len(), Default::default);
+
+    plan.fft_in_place(&mut a);
+    plan.fft_in_place(&mut b);
+
+    a.iter_mut().zip(b.iter()).for_each(|(x, y)| *x *= y);
+
+    plan.inverse_fft_in_place(&mut a);
+    a.truncate(new_size);
+    a
+}
+
+/// Computes [`fft`] of a real sequence with a single complex FFT of half the length.
+///
+/// Only the first `n / 2 + 1` values are returned, the rest are their complex conjugates in
+/// reverse order, and an empty input has none. Odd lengths cannot be packed and fall back to a
+/// full complex FFT.
+pub fn real_fft(input: &[f64]) -> Vec<Complex<f64>> {
+    PLANNER.with(|planner| planner.plan_real(input.len()).real_fft(input))
+}
+
+/// Recovers a real sequence of length `n` from the `n / 2 + 1` values produced by
+/// [`real_fft`], again with a single complex FFT of half the length.
+pub fn inverse_real_fft(input: &[Complex<f64>], n: usize) -> Vec<f64> {
+    PLANNER.with(|planner| planner.plan_real(n).inverse_real_fft(input))
+}
+
+/// Computes the linear convolution of two real sequences with [`real_fft`], doing about
+/// half the work of [`convolve`].
+pub fn convolve_real(a: &[f64], b: &[f64]) -> Vec<f64> {
+    PLANNER.with(|planner| convolve_real_with(a, b, planner))
+}
+
+/// Same as [`convolve_real`], taking the transform from `planner`.
+pub fn convolve_real_with(a: &[f64], b: &[f64], planner: &FftPlanner) -> Vec<f64> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+
+    let new_size = a.len() + b.len() - 1;
+    // Even lengths keep the half-length packing available.
+    let plan = planner.plan_real(2 * good_size(new_size.div_ceil(2)));
+
+    let mut a = a.to_owned();
+    let mut b = b.to_owned();
+    a.resize(plan.len(), 0.);
+    b.resize(plan.len(), 0.);
+
+    let new_points: Vec<Complex<f64>> =
+        plan.real_fft(&a).iter().zip(plan.real_fft(&b).iter()).map(|(x, y)| x * y).collect();
+
+    let mut new_coeffs = plan.inverse_real_fft(&new_points);
+    new_coeffs.truncate(new_size);
+    new_coeffs
+}
+
+/// Returns the cheapest transform length of at least `n` among the numbers whose prime
+/// factors are all at most 7, which is never more than `n.next_power_of_two()`.
+///
+/// The cost of a length is estimated as the length times the sum of its prime factors,
+/// matching the work done by the mixed-radix kernel.
+pub fn good_size(n: usize) -> usize {
+    let limit = n.next_power_of_two();
+    let cost = |len: usize| len * factorize(len).iter().sum::<usize>();
+
+    let mut best = limit;
+    let mut best_cost = cost(limit);
+    let mut pow7 = 1;
+    while pow7 <= limit {
+        let mut pow5 = pow7;
+        while pow5 <= limit {
+            let mut pow3 = pow5;
+            while pow3 <= limit {
+                let mut len = pow3;
+                while len < n {
+                    len *= 2;
+                }
+                if len <= limit && cost(len) < best_cost {
+                    best = len;
+                    best_cost = cost(len);
+                }
+                pow3 *= 3;
+            }
+            pow5 *= 5;
+        }
+        pow7 *= 7;
+    }
+
+    best
+}
+
+/// Returns the prime factors of `n` in ascending order, with multiplicity.
+fn factorize(mut n: usize) -> Vec<usize> {
+    let mut factors = Vec::new();
+    let mut p = 2;
+    while p * p <= n {
+        while n % p == 0 {
+            factors.push(p);
+            n /= p;
+        }
+        p += 1;
+    }
+    if n > 1 {
+        factors.push(n);
+    }
+
+    factors
+}
+
+/// Returns `e^(sign * 2 pi i k / n)` to within an ulp or so of each component.
+///
+/// The angle is reduced to the first octant with exact integer arithmetic before calling
+/// `sin`/`cos`, so the quarter turns are exact and the symmetries of the roots of unity hold
+/// bit for bit.
+fn root_of_unity(k: usize, n: usize, sign: f64) -> Complex<f64> {
+    let k = k % n;
+    let (octant, rem) = (8 * k / n, 8 * k % n);
+
+    let (re, im) = if octant % 2 == 0 {
+        let part = FRAC_PI_4 * rem as f64 / n as f64;
+        (part.cos(), part.sin())
+    } else {
+        let part = FRAC_PI_4 * (n - rem) as f64 / n as f64;
+        (part.sin(), part.cos())
+    };
+
+    let (re, im) = match octant / 2 {
+        0 => (re, im),
+        1 => (-im, re),
+        2 => (-re, -im),
+        _ => (im, -re),
+    };
+
+    Complex::new(re, sign * im)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crt::convolve_i64;
+    use crate::test_util::{max_error, random_vec};
+
+    /// Checks the documented error of [`convolve`] and [`convolve_real`] on 2^19 random
+    /// integers in `[-1024, 1024)` per input against the exact product.
+    #[test]
+    fn convolution_error_bound_at_large_size() {
+        let len = 1 << 19;
+        let random = |seed| -> Vec<i64> {
+            random_vec::<u64>(len, seed).into_iter().map(|x| (x >> 53) as i64 - 1024).collect()
+        };
+        let (a, b) = (random(1), random(2));
+        let exact: Vec<f64> = convolve_i64(&a, &b).into_iter().map(|x| x as f64).collect();
+
+        let real = |x: &[i64]| -> Vec<f64> { x.iter().map(|&c| c as f64).collect() };
+        let error = max_error(&convolve_real(&real(&a), &real(&b)), &exact);
+        assert!(error < 1e-6, "real convolution error {}", error);
+
+        let complex = |x: &[f64]| -> Vec<Complex<f64>> { x.iter().map(|&c| c.into()).collect() };
+        let error =
+            max_error(&convolve(&complex(&real(&a)), &complex(&real(&b))), &complex(&exact));
+        assert!(error < 1e-6, "complex convolution error {}", error);
+    }
+}
@@ -0,0 +1,171 @@
+use super::with_plan;
+use num::complex::Complex;
+
+/// Side of the square tiles the transposition copies at a time, small enough that a tile of
+/// the source and one of the destination stay in the L1 cache together.
+const TILE: usize = 32;
+
+/// Computes the two-dimensional [`fft`](super::fft) of a row-major `rows x cols` array,
+/// transforming every row and then every column.
+///
+/// Panics if `input` does not have `rows * cols` entries.
+pub fn fft_2d(input: &[Complex<f64>], rows: usize, cols: usize) -> Vec<Complex<f64>> {
+    fft_nd(input, &[rows, cols])
+}
+
+/// Recovers the array from the values produced by [`fft_2d`].
+pub fn inverse_fft_2d(input: &[Complex<f64>], rows: usize, cols: usize) -> Vec<Complex<f64>> {
+    inverse_fft_nd(input, &[rows, cols])
+}
+
+/// Computes the multidimensional [`fft`](super::fft) of a row-major array of the given
+/// shape, applying the one-dimensional transform along each axis in turn.
+///
+/// Only the last axis is contiguous, so rather than gathering strided columns, each pass
+/// transforms the contiguous rows and then transposes the array in cache-sized tiles, which
+/// moves the last axis to the front. After one pass per axis the original layout is back.
+///
+/// Panics if `input` does not have as many entries as the product of `shape`.
+pub fn fft_nd(input: &[Complex<f64>], shape: &[usize]) -> Vec<Complex<f64>> {
+    transform(input, shape, false)
+}
+
+/// Recovers the array from the values produced by [`fft_nd`].
+pub fn inverse_fft_nd(input: &[Complex<f64>], shape: &[usize]) -> Vec<Complex<f64>> {
+    transform(input, shape, true)
+}
+
+fn transform(input: &[Complex<f64>], shape: &[usize], inverse: bool) -> Vec<Complex<f64>> {
+    assert_eq!(
+        input.len(),
+        shape.iter().product::<usize>(),
+        "input length does not match the shape"
+    );
+
+    let mut buf = input.to_vec();
+    if buf.is_empty() {
+        return buf;
+    }
+
+    let mut scratch = vec![Complex::default(); buf.len()];
+    for &len in shape.iter().rev() {
+        with_plan(len, |plan| {
+            for row in buf.chunks_exact_mut(len) {
+                if inverse {
+                    plan.inverse_fft_in_place(row);
+                } else {
+                    plan.fft_in_place(row);
+                }
+            }
+        });
+
+        // With a single row or column the transposition leaves the entries in place.
+        let rows = buf.len() / len;
+        if rows > 1 && len > 1 {
+            transpose(&buf, &mut scratch, rows, len);
+            std::mem::swap(&mut buf, &mut scratch);
+        }
+    }
+
+    buf
+}
+
+/// Writes the transpose of the row-major `rows x cols` matrix `src` to `dst`, one tile at a
+/// time so that both sides are walked with cache-line locality.
+fn transpose(src: &[Complex<f64>], dst: &mut [Complex<f64>], rows: usize, cols: usize) {
+    for r0 in (0..rows).step_by(TILE) {
+        for c0 in (0..cols).step_by(TILE) {
+            for r in r0..(r0 + TILE).min(rows) {
+                for c in c0..(c0 + TILE).min(cols) {
+                    dst[c * rows + r] = src[r * cols + c];
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fft::fft;
+    use crate::test_util::{max_error, random_vec};
+
+    /// Sums every entry against every output index, with the root of unity [`fft`] uses for
+    /// each axis length.
+    fn naive_dft(input: &[Complex<f64>], shape: &[usize]) -> Vec<Complex<f64>> {
+        let roots: Vec<Complex<f64>> = shape
+            .iter()
+            .map(|&n| {
+                let mut unit = vec![Complex::default(); n];
+                unit[1 % n] = Complex::new(1., 0.);
+                fft(&unit)[1 % n]
+            })
+            .collect();
+        let unravel = |mut index: usize| -> Vec<usize> {
+            let mut ret = vec![0; shape.len()];
+            for (i, &n) in ret.iter_mut().zip(shape).rev() {
+                *i = index % n;
+                index /= n;
+            }
+            ret
+        };
+
+        (0..input.len())
+            .map(|k| {
+                let k = unravel(k);
+                input.iter().enumerate().fold(Complex::default(), |acc, (j, &x)| {
+                    let j = unravel(j);
+                    let twiddle = (0..shape.len()).fold(Complex::new(1., 0.), |w, a| {
+                        w * roots[a].powu((j[a] * k[a] % shape[a]) as u32)
+                    });
+                    acc + x * twiddle
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn matches_naive_dft() {
+        for (seed, shape) in
+            [vec![3, 4, 5], vec![7, 6], vec![2, 1, 9], vec![11], vec![40, 33]].iter().enumerate()
+        {
+            let input: Vec<Complex<f64>> = random_vec(shape.iter().product(), seed as u64);
+            let error = max_error(&fft_nd(&input, shape), &naive_dft(&input, shape));
+            assert!(error < 1e-9, "shape {:?}: error {}", shape, error);
+        }
+    }
+
+    #[test]
+    fn fft_2d_matches_fft_nd() {
+        let input: Vec<Complex<f64>> = random_vec(6 * 10, 7);
+        assert_eq!(fft_2d(&input, 6, 10), fft_nd(&input, &[6, 10]));
+        assert_eq!(inverse_fft_2d(&input, 6, 10), inverse_fft_nd(&input, &[6, 10]));
+    }
+
+    #[test]
+    fn inverse_round_trips() {
+        for (seed, shape) in
+            [vec![3, 4, 5], vec![40, 33], vec![1, 64, 1], vec![5, 3, 2, 7]].iter().enumerate()
+        {
+            let input: Vec<Complex<f64>> = random_vec(shape.iter().product(), seed as u64 + 10);
+            let error = max_error(&inverse_fft_nd(&fft_nd(&input, shape), shape), &input);
+            assert!(error < 1e-12, "shape {:?}: error {}", shape, error);
+        }
+        assert!(fft_nd(&[], &[0, 3]).is_empty());
+    }
+
+    #[test]
+    fn transpose_across_tile_boundaries() {
+        for &(rows, cols) in &[(1, 5), (5, 1), (TILE, TILE), (37, 70), (TILE + 1, 2 * TILE - 1)] {
+            let src: Vec<Complex<f64>> =
+                (0..rows * cols).map(|i| Complex::new(i as f64, 0.)).collect();
+            let mut dst = vec![Complex::default(); src.len()];
+            transpose(&src, &mut dst, rows, cols);
+            for r in 0..rows {
+                for c in 0..cols {
+                    assert_eq!(dst[c * rows + r], src[r * cols + c], "{} x {}", rows, cols);
+                }
+            }
+        }
+    }
+}
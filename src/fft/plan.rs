@@ -0,0 +1,479 @@
+use super::{factorize, root_of_unity};
+use num::complex::Complex;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// A precomputed transform of a fixed length.
+///
+/// Twiddle factors, the digit-reversal permutation and, for Bluestein lengths, the
+/// transformed chirp are computed once in [`FftPlan::new`], so a plan can be reused for any
+/// number of transforms and shared between threads.
+///
+/// The `_in_place` and `_into` methods do not allocate, except for lengths with a prime
+/// factor above 7, where Bluestein's algorithm needs a scratch buffer of about twice the
+/// length. [`good_size`](super::good_size) picks lengths that avoid it.
+#[derive(Debug, Clone)]
+pub struct FftPlan {
+    len: usize,
+    kind: Kind,
+}
+
+#[derive(Debug, Clone)]
+enum Kind {
+    /// Iterative decimation-in-time Cooley-Tukey for lengths whose prime factors are at
+    /// most 7; a power of two is the radix-2 special case.
+    CooleyTukey {
+        factors: Vec<usize>,
+        /// `permutation[i]` is the input index that lands at `i` before the first stage,
+        /// which is `i` with its mixed-radix digits reversed.
+        permutation: Vec<usize>,
+        /// The same permutation as a sequence of swaps, for applying it in place.
+        swaps: Vec<(usize, usize)>,
+        /// All `len` powers of the `len`-th root of unity.
+        twiddles: Vec<Complex<f64>>,
+    },
+    Bluestein {
+        inner: Box<FftPlan>,
+        chirp: Vec<Complex<f64>>,
+        /// The inner transform of the conjugated chirp, laid out for a cyclic convolution.
+        chirp_points: Vec<Complex<f64>>,
+    },
+}
+
+impl FftPlan {
+    pub fn new(len: usize) -> Self {
+        let factors = factorize(len);
+
+        let kind = if factors.iter().all(|&p| p <= 7) {
+            let permutation = digit_reversal(len, &factors);
+            let swaps = permutation_swaps(&permutation);
+
+            Kind::CooleyTukey { factors, permutation, swaps, twiddles: twiddles(len, len) }
+        } else {
+            let m = (2 * len - 1).next_power_of_two();
+            let inner = Box::new(FftPlan::new(m));
+
+            // `j^2` is reduced modulo `2n` first, since the chirp has period `2n` in `j^2`.
+            let chirp: Vec<Complex<f64>> =
+                (0..len).map(|j| root_of_unity(j * j % (2 * len), 2 * len, 1.)).collect();
+
+            let mut chirp_points = vec![Complex::default(); m];
+            chirp_points[0] = chirp[0].conj();
+            for j in 1..len {
+                chirp_points[j] = chirp[j].conj();
+                chirp_points[m - j] = chirp[j].conj();
+            }
+            inner.fft_in_place(&mut chirp_points);
+
+            Kind::Bluestein { inner, chirp, chirp_points }
+        };
+
+        FftPlan { len, kind }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Same as [`fft`](super::fft), for inputs of length [`FftPlan::len`].
+    pub fn fft(&self, input: &[Complex<f64>]) -> Vec<Complex<f64>> {
+        let mut ret = vec![Complex::default(); self.len];
+        self.fft_into(input, &mut ret);
+        ret
+    }
+
+    /// Same as [`inverse_fft`](super::inverse_fft), for inputs of length [`FftPlan::len`].
+    pub fn inverse_fft(&self, input: &[Complex<f64>]) -> Vec<Complex<f64>> {
+        let mut ret = vec![Complex::default(); self.len];
+        self.inverse_fft_into(input, &mut ret);
+        ret
+    }
+
+    /// Replaces `buf` with its transform.
+    pub fn fft_in_place(&self, buf: &mut [Complex<f64>]) {
+        self.transform_in_place(buf, false);
+    }
+
+    /// Replaces `buf` with its inverse transform.
+    pub fn inverse_fft_in_place(&self, buf: &mut [Complex<f64>]) {
+        self.transform_in_place(buf, true);
+        normalize(buf);
+    }
+
+    /// Writes the transform of `input` to `output`, leaving `input` untouched.
+    pub fn fft_into(&self, input: &[Complex<f64>], output: &mut [Complex<f64>]) {
+        self.transform_into(input, output, false);
+    }
+
+    /// Writes the inverse transform of `input` to `output`, leaving `input` untouched.
+    pub fn inverse_fft_into(&self, input: &[Complex<f64>], output: &mut [Complex<f64>]) {
+        self.transform_into(input, output, true);
+        normalize(output);
+    }
+
+    /// Runs the unnormalized transform, with conjugated roots of unity if `inverse` is set.
+    fn transform_in_place(&self, buf: &mut [Complex<f64>], inverse: bool) {
+        assert_eq!(buf.len(), self.len, "buffer length does not match the plan");
+
+        match &self.kind {
+            Kind::CooleyTukey { factors, swaps, twiddles, .. } => {
+                swaps.iter().for_each(|&(i, j)| buf.swap(i, j));
+                butterflies(buf, factors, twiddles, inverse);
+            }
+            Kind::Bluestein { .. } => {
+                let input = buf.to_owned();
+                self.transform_into(&input, buf, inverse);
+            }
+        }
+    }
+
+    fn transform_into(&self, input: &[Complex<f64>], output: &mut [Complex<f64>], inverse: bool) {
+        assert_eq!(input.len(), self.len, "input length does not match the plan");
+        assert_eq!(output.len(), self.len, "output length does not match the plan");
+
+        match &self.kind {
+            Kind::CooleyTukey { factors, permutation, twiddles, .. } => {
+                output.iter_mut().zip(permutation.iter()).for_each(|(x, &i)| *x = input[i]);
+                butterflies(output, factors, twiddles, inverse);
+            }
+            Kind::Bluestein { inner, chirp, chirp_points } => {
+                // The inverse transform is the conjugate of the forward transform of the
+                // conjugated input, which keeps a single chirp table.
+                let conj = |x: Complex<f64>| if inverse { x.conj() } else { x };
+                let m = inner.len();
+
+                let mut a = vec![Complex::default(); m];
+                a.iter_mut()
+                    .zip(input.iter().zip(chirp.iter()))
+                    .for_each(|(a, (x, c))| *a = conj(*x) * c);
+
+                inner.fft_in_place(&mut a);
+                a.iter_mut().zip(chirp_points.iter()).for_each(|(x, y)| *x *= y);
+                inner.transform_in_place(&mut a, true);
+
+                output
+                    .iter_mut()
+                    .zip(a.iter().zip(chirp.iter()))
+                    .for_each(|(out, (x, c))| *out = conj(x * c / m as f64));
+            }
+        }
+    }
+}
+
+/// A precomputed real-to-complex transform of a fixed length, see [`real_fft`](super::real_fft).
+#[derive(Debug, Clone)]
+pub struct RealFftPlan {
+    len: usize,
+    /// The half-length complex transform, or the full-length one for odd lengths.
+    inner: FftPlan,
+    /// The first `len / 2 + 1` powers of the `len`-th root of unity.
+    twiddles: Vec<Complex<f64>>,
+}
+
+impl RealFftPlan {
+    pub fn new(len: usize) -> Self {
+        if len % 2 == 1 || len == 0 {
+            RealFftPlan { len, inner: FftPlan::new(len), twiddles: Vec::new() }
+        } else {
+            RealFftPlan { len, inner: FftPlan::new(len / 2), twiddles: twiddles(len, len / 2 + 1) }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Same as [`real_fft`](super::real_fft), for inputs of length [`RealFftPlan::len`].
+    pub fn real_fft(&self, input: &[f64]) -> Vec<Complex<f64>> {
+        let n = self.len;
+        assert_eq!(input.len(), n, "input length does not match the plan");
+
+        if n == 0 {
+            return Vec::new();
+        }
+        if n % 2 == 1 {
+            let full: Vec<Complex<f64>> = input.iter().map(|&x| x.into()).collect();
+            let mut points = self.inner.fft(&full);
+            points.truncate(n / 2 + 1);
+            return points;
+        }
+
+        let half = n / 2;
+        let packed: Vec<Complex<f64>> =
+            input.chunks_exact(2).map(|pair| Complex::new(pair[0], pair[1])).collect();
+        let points = self.inner.fft(&packed);
+
+        (0..=half)
+            .map(|k| {
+                let z = points[k % half];
+                let z_rev = points[(half - k) % half].conj();
+                let even = (z + z_rev) * 0.5;
+                let odd = (z - z_rev) * Complex::new(0., -0.5);
+                even + self.twiddles[k] * odd
+            })
+            .collect()
+    }
+
+    /// Same as [`inverse_real_fft`](super::inverse_real_fft), for outputs of length
+    /// [`RealFftPlan::len`].
+    pub fn inverse_real_fft(&self, input: &[Complex<f64>]) -> Vec<f64> {
+        let n = self.len;
+        if n == 0 {
+            assert!(input.is_empty(), "input length does not match the plan");
+            return Vec::new();
+        }
+        assert_eq!(input.len(), n / 2 + 1, "input length does not match the plan");
+
+        if n % 2 == 1 {
+            let mut full: Vec<Complex<f64>> = input.to_owned();
+            full.extend(input[1..].iter().rev().map(|x| x.conj()));
+            return self.inner.inverse_fft(&full).into_iter().map(|z| z.re).collect();
+        }
+
+        let half = n / 2;
+        let packed: Vec<Complex<f64>> = (0..half)
+            .map(|k| {
+                let x = input[k];
+                let x_rev = input[half - k].conj();
+                let even = (x + x_rev) * 0.5;
+                let odd = (x - x_rev) * 0.5 * self.twiddles[k].conj();
+                even + Complex::<f64>::i() * odd
+            })
+            .collect();
+
+        self.inner.inverse_fft(&packed).into_iter().flat_map(|z| [z.re, z.im]).collect()
+    }
+}
+
+/// A thread-safe cache of [`FftPlan`]s and [`RealFftPlan`]s keyed by length.
+///
+/// Plans are built on first use and shared afterwards, so a single planner can serve every
+/// product of a workload, including from several threads at once. They are kept until the
+/// planner is dropped or [`FftPlanner::clear`] is called, at a cost of roughly 40 bytes per
+/// point of each length seen.
+#[derive(Debug, Default)]
+pub struct FftPlanner {
+    plans: Mutex<HashMap<usize, Arc<FftPlan>>>,
+    real_plans: Mutex<HashMap<usize, Arc<RealFftPlan>>>,
+}
+
+impl FftPlanner {
+    pub fn new() -> Self {
+        FftPlanner::default()
+    }
+
+    /// Returns the plan for `len`, building it if this planner has not seen the length yet.
+    pub fn plan(&self, len: usize) -> Arc<FftPlan> {
+        let mut plans = self.plans.lock().unwrap();
+        plans.entry(len).or_insert_with(|| Arc::new(FftPlan::new(len))).clone()
+    }
+
+    /// Returns the real-input plan for `len`, building it if needed.
+    pub fn plan_real(&self, len: usize) -> Arc<RealFftPlan> {
+        let mut plans = self.real_plans.lock().unwrap();
+        plans.entry(len).or_insert_with(|| Arc::new(RealFftPlan::new(len))).clone()
+    }
+
+    /// Drops every cached plan. Plans already handed out stay valid until their last
+    /// reference goes away.
+    pub fn clear(&self) {
+        self.plans.lock().unwrap().clear();
+        self.real_plans.lock().unwrap().clear();
+    }
+}
+
+/// Returns the first `count` powers of the `n`-th root of unity, each computed directly
+/// rather than by repeated multiplication so the error does not grow with `n`.
+fn twiddles(n: usize, count: usize) -> Vec<Complex<f64>> {
+    (0..count).map(|k| root_of_unity(k, n, 1.)).collect()
+}
+
+fn twiddle(twiddles: &[Complex<f64>], k: usize, inverse: bool) -> Complex<f64> {
+    if inverse {
+        twiddles[k].conj()
+    } else {
+        twiddles[k]
+    }
+}
+
+fn normalize(buf: &mut [Complex<f64>]) {
+    let n = buf.len() as f64;
+    buf.iter_mut().for_each(|x| *x /= n);
+}
+
+/// Returns the permutation taking the input to the order the butterflies expect: the
+/// output index `sum(q_l * len / (r_0 * ... * r_l))` reads the input index
+/// `sum(q_l * r_0 * ... * r_(l - 1))`, where `r_l` are the `factors`.
+fn digit_reversal(len: usize, factors: &[usize]) -> Vec<usize> {
+    (0..len)
+        .map(|i| {
+            let (mut size, mut radix_product, mut ret) = (len, 1, 0);
+            for &radix in factors {
+                size /= radix;
+                ret += (i / size) % radix * radix_product;
+                radix_product *= radix;
+            }
+            ret
+        })
+        .collect()
+}
+
+/// Decomposes `permutation` into swaps which, applied in order, move `x[permutation[i]]`
+/// to index `i`.
+fn permutation_swaps(permutation: &[usize]) -> Vec<(usize, usize)> {
+    let mut visited = vec![false; permutation.len()];
+    let mut swaps = Vec::new();
+
+    for start in 0..permutation.len() {
+        let mut i = start;
+        while !visited[i] {
+            visited[i] = true;
+            if permutation[i] != start {
+                swaps.push((i, permutation[i]));
+            }
+            i = permutation[i];
+        }
+    }
+
+    swaps
+}
+
+/// Combines digit-reversed input in place, one stage per factor starting from the last,
+/// each merging `radix` transforms of length `m` into one of length `m * radix`.
+fn butterflies(
+    buf: &mut [Complex<f64>],
+    factors: &[usize],
+    twiddles: &[Complex<f64>],
+    inverse: bool,
+) {
+    let n = buf.len();
+    let mut m = 1;
+
+    for &radix in factors.iter().rev() {
+        let len = m * radix;
+        let stride = n / len;
+
+        for block in buf.chunks_exact_mut(len) {
+            if radix == 2 {
+                for k1 in 0..m {
+                    let a = block[k1];
+                    let b = twiddle(twiddles, k1 * stride, inverse) * block[k1 + m];
+
+                    block[k1] = a + b;
+                    block[k1 + m] = a - b;
+                }
+                continue;
+            }
+
+            // Output `k1 + k2 * m` only depends on the entries `k1 + q * m` of the
+            // sub-transforms, so each group of `radix` values is combined on its own.
+            let mut parts = [Complex::default(); 7];
+            for k1 in 0..m {
+                for (q, part) in parts.iter_mut().take(radix).enumerate() {
+                    *part = twiddle(twiddles, q * k1 * stride, inverse) * block[k1 + q * m];
+                }
+
+                for k2 in 0..radix {
+                    block[k1 + k2 * m] = parts
+                        .iter()
+                        .take(radix)
+                        .enumerate()
+                        .map(|(q, part)| {
+                            part * twiddle(twiddles, q * k2 % radix * (n / radix), inverse)
+                        })
+                        .sum();
+                }
+            }
+        }
+
+        m = len;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::max_error;
+
+    fn naive_dft(input: &[Complex<f64>]) -> Vec<Complex<f64>> {
+        let n = input.len();
+        (0..n)
+            .map(|k| input.iter().enumerate().map(|(j, x)| x * root_of_unity(j * k, n, 1.)).sum())
+            .collect()
+    }
+
+    fn sample(len: usize) -> Vec<Complex<f64>> {
+        (0..len)
+            .map(|i| Complex::new(((i * 7) % 13) as f64 - 6., ((i * 5) % 11) as f64 - 5.))
+            .collect()
+    }
+
+    #[test]
+    fn mixed_radix_matches_naive_dft() {
+        for len in [1, 2, 4, 6, 9, 12, 25, 49, 60, 210, 343, 1024, 2520] {
+            let input = sample(len);
+            let plan = FftPlan::new(len);
+            assert!(matches!(plan.kind, Kind::CooleyTukey { .. }));
+
+            let values = plan.fft(&input);
+            let tolerance = 1e-12 * len as f64 * 16.;
+            assert!(max_error(&values, &naive_dft(&input)) < tolerance, "len {}", len);
+            assert!(max_error(&plan.inverse_fft(&values), &input) < 1e-9);
+        }
+    }
+
+    #[test]
+    fn bluestein_matches_naive_dft() {
+        for len in [11, 13, 22, 97, 121, 1009] {
+            let input = sample(len);
+            let plan = FftPlan::new(len);
+            assert!(matches!(plan.kind, Kind::Bluestein { .. }));
+
+            let values = plan.fft(&input);
+            let tolerance = 1e-12 * len as f64 * 16.;
+            assert!(max_error(&values, &naive_dft(&input)) < tolerance, "len {}", len);
+            assert!(max_error(&plan.inverse_fft(&values), &input) < 1e-9);
+
+            let mut buf = input.clone();
+            plan.fft_in_place(&mut buf);
+            assert_eq!(buf, values);
+        }
+    }
+
+    #[test]
+    fn planner_reuses_plans_until_cleared() {
+        let planner = FftPlanner::new();
+        let plan = planner.plan(12);
+        assert!(Arc::ptr_eq(&plan, &planner.plan(12)));
+
+        planner.clear();
+        assert!(!Arc::ptr_eq(&plan, &planner.plan(12)));
+        assert_eq!(plan.len(), 12);
+    }
+
+    #[test]
+    fn real_plan_matches_complex_plan() {
+        for len in [0, 1, 2, 3, 8, 15, 30, 64, 97] {
+            let input: Vec<f64> = (0..len).map(|i| ((i * 7) % 13) as f64 - 6.).collect();
+            let plan = RealFftPlan::new(len);
+
+            let values = plan.real_fft(&input);
+            let complex: Vec<Complex<f64>> = input.iter().map(|&x| x.into()).collect();
+            let mut expected = FftPlan::new(len).fft(&complex);
+            expected.truncate(if len == 0 { 0 } else { len / 2 + 1 });
+            assert!(max_error(&values, &expected) < 1e-9, "len {}", len);
+
+            let back = plan.inverse_real_fft(&values);
+            assert_eq!(back.len(), len);
+            assert!(back.iter().zip(&input).all(|(x, y)| (x - y).abs() < 1e-9));
+        }
+    }
+}
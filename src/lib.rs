@@ -0,0 +1,20 @@
+mod coefficient;
+mod convolution;
+pub mod crt;
+pub mod fft;
+pub mod ntt;
+mod polynomial;
+#[cfg(test)]
+mod test_util;
+
+pub use coefficient::{Coefficient, Conjugate, Field, Magnitude, SquareRoot};
+pub use convolution::{autocorrelate, convolve, correlate, cyclic_convolve};
+pub use crt::{convolve_bigint, convolve_i64, convolve_u64};
+pub use ntt::ModInt;
+#[cfg(feature = "serde")]
+pub use polynomial::serde_sparse;
+pub use polynomial::{
+    FormatOptions, ModPolynomial, MultiPolynomial, Notation, Order, ParseErrorKind,
+    ParsePolynomialError, Polynomial, PolynomialDisplay, Root, RootOptions, SparsePolynomial,
+    MAX_EXPONENT,
+};
@@ -0,0 +1,455 @@
+use num::{One, Zero};
+use std::fmt;
+use std::num::ParseIntError;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+/// NTT-friendly primes below `2^32` paired with their smallest primitive root.
+pub const NTT_PRIMES: [(u32, u32); 8] = [
+    (998_244_353, 3),
+    (1_004_535_809, 3),
+    (754_974_721, 11),
+    (167_772_161, 3),
+    (469_762_049, 3),
+    (2_013_265_921, 31),
+    (2_281_701_377, 3),
+    (3_221_225_473, 5),
+];
+
+pub(crate) const fn pow_mod(mut base: u64, mut exp: u64, p: u64) -> u64 {
+    let mut ret = 1 % p;
+    base %= p;
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            ret = ret * base % p;
+        }
+        base = base * base % p;
+        exp >>= 1;
+    }
+
+    ret
+}
+
+/// Returns the smallest primitive root of the prime `p`, looking it up in [`NTT_PRIMES`]
+/// before falling back to a search.
+pub const fn primitive_root(p: u32) -> u32 {
+    let mut i = 0;
+    while i < NTT_PRIMES.len() {
+        if NTT_PRIMES[i].0 == p {
+            return NTT_PRIMES[i].1;
+        }
+        i += 1;
+    }
+
+    let p = p as u64;
+    let mut factors = [0u64; 16];
+    let mut count = 0;
+    let mut m = p - 1;
+    let mut d = 2;
+    while d * d <= m {
+        if m % d == 0 {
+            factors[count] = d;
+            count += 1;
+            while m % d == 0 {
+                m /= d;
+            }
+        }
+        d += 1;
+    }
+    if m > 1 {
+        factors[count] = m;
+        count += 1;
+    }
+
+    let mut g = 2;
+    loop {
+        let mut i = 0;
+        while i < count && pow_mod(g, (p - 1) / factors[i], p) != 1 {
+            i += 1;
+        }
+        if i == count {
+            return g as u32;
+        }
+        g += 1;
+    }
+}
+
+/// An integer modulo the prime `P`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ModInt<const P: u32>(u32);
+
+/// The usual NTT prime `119 * 2^23 + 1`.
+pub type Mod998244353 = ModInt<998_244_353>;
+
+impl<const P: u32> ModInt<P> {
+    pub const MODULUS: u32 = P;
+    pub const PRIMITIVE_ROOT: u32 = primitive_root(P);
+    /// The largest `k` such that `2^k` divides `P - 1`, which bounds the NTT length.
+    pub const MAX_LOG: u32 = (P - 1).trailing_zeros();
+
+    pub fn new(value: u64) -> Self {
+        ModInt((value % P as u64) as u32)
+    }
+
+    pub fn value(self) -> u32 {
+        self.0
+    }
+
+    pub fn pow(self, exp: u64) -> Self {
+        ModInt(pow_mod(self.0 as u64, exp, P as u64) as u32)
+    }
+
+    /// Returns the multiplicative inverse. Panics on zero.
+    pub fn inv(self) -> Self {
+        assert!(self.0 != 0, "zero has no inverse modulo {}", P);
+        self.pow(P as u64 - 2)
+    }
+
+    /// Returns a square root by Tonelli-Shanks, or `None` for quadratic non-residues.
+    pub fn sqrt(self) -> Option<Self> {
+        if self.0 == 0 || P == 2 {
+            return Some(self);
+        }
+        if self.pow(((P - 1) / 2) as u64) != ModInt::one() {
+            return None;
+        }
+
+        let mut m = Self::MAX_LOG;
+        let mut c = ModInt(Self::PRIMITIVE_ROOT).pow(((P - 1) >> m) as u64);
+        let mut t = self.pow(((P - 1) >> m) as u64);
+        let mut r = self.pow((((P - 1) >> m) as u64).div_ceil(2));
+
+        while t != ModInt::one() {
+            let mut i = 0;
+            let mut t_pow = t;
+            while t_pow != ModInt::one() {
+                t_pow *= t_pow;
+                i += 1;
+            }
+
+            let b = c.pow(1 << (m - i - 1));
+            m = i;
+            c = b * b;
+            t *= c;
+            r *= b;
+        }
+
+        Some(r)
+    }
+
+    /// Returns a primitive `2^log`-th root of unity.
+    pub fn root_of_unity(log: u32) -> Self {
+        assert!(log <= Self::MAX_LOG, "2^{} does not divide {} - 1", log, P);
+        ModInt(Self::PRIMITIVE_ROOT).pow(((P - 1) >> log) as u64)
+    }
+}
+
+impl<const P: u32> From<u32> for ModInt<P> {
+    fn from(value: u32) -> Self {
+        ModInt::new(value as u64)
+    }
+}
+
+impl<const P: u32> From<u64> for ModInt<P> {
+    fn from(value: u64) -> Self {
+        ModInt::new(value)
+    }
+}
+
+impl<const P: u32> From<i64> for ModInt<P> {
+    fn from(value: i64) -> Self {
+        ModInt(value.rem_euclid(P as i64) as u32)
+    }
+}
+
+impl<const P: u32> From<i32> for ModInt<P> {
+    fn from(value: i32) -> Self {
+        ModInt::from(value as i64)
+    }
+}
+
+impl<const P: u32> FromStr for ModInt<P> {
+    type Err = ParseIntError;
+
+    /// Parses a signed integer and reduces it modulo `P`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse::<i64>().map(ModInt::from)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<const P: u32> serde::Serialize for ModInt<P> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u32(self.0)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, const P: u32> serde::Deserialize<'de> for ModInt<P> {
+    /// Accepts only reduced values, so that data written for one modulus is not silently
+    /// reinterpreted under another.
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::{Error, Unexpected};
+
+        let value = u32::deserialize(deserializer)?;
+        if value < P {
+            Ok(ModInt(value))
+        } else {
+            Err(D::Error::invalid_value(
+                Unexpected::Unsigned(value as u64),
+                &"a value below the modulus",
+            ))
+        }
+    }
+}
+
+impl<const P: u32> fmt::Display for ModInt<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl<const P: u32> Add for ModInt<P> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        ModInt(((self.0 as u64 + rhs.0 as u64) % P as u64) as u32)
+    }
+}
+
+impl<const P: u32> Sub for ModInt<P> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        ModInt(((self.0 as u64 + P as u64 - rhs.0 as u64) % P as u64) as u32)
+    }
+}
+
+impl<const P: u32> Mul for ModInt<P> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        ModInt((self.0 as u64 * rhs.0 as u64 % P as u64) as u32)
+    }
+}
+
+impl<const P: u32> Div for ModInt<P> {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self {
+        self * rhs.inv()
+    }
+}
+
+impl<const P: u32> Neg for ModInt<P> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        ModInt::zero() - self
+    }
+}
+
+macro_rules! assign_ops {
+    ($($imp:ident, $method:ident, $op:tt;)*) => {
+        $(
+            impl<const P: u32> $imp for ModInt<P> {
+                fn $method(&mut self, rhs: Self) {
+                    *self = *self $op rhs;
+                }
+            }
+        )*
+    };
+}
+
+assign_ops! {
+    AddAssign, add_assign, +;
+    SubAssign, sub_assign, -;
+    MulAssign, mul_assign, *;
+    DivAssign, div_assign, /;
+}
+
+impl<const P: u32> Zero for ModInt<P> {
+    fn zero() -> Self {
+        ModInt(0)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl<const P: u32> One for ModInt<P> {
+    fn one() -> Self {
+        ModInt(1 % P)
+    }
+}
+
+/// Evaluates the polynomial with coefficients `input` at the powers of a primitive
+/// `n`-th root of unity modulo `P`.
+///
+/// `input.len()` must be a power of two dividing `P - 1`.
+pub fn ntt<const P: u32>(input: &[ModInt<P>]) -> Vec<ModInt<P>> {
+    let mut ret = input.to_owned();
+    transform(&mut ret, false);
+    ret
+}
+
+/// Recovers coefficients from the values produced by [`ntt`].
+pub fn inverse_ntt<const P: u32>(input: &[ModInt<P>]) -> Vec<ModInt<P>> {
+    let mut ret = input.to_owned();
+    transform(&mut ret, true);
+
+    let n_inv = ModInt::<P>::from(ret.len() as u64).inv();
+    ret.iter_mut().for_each(|x| *x *= n_inv);
+    ret
+}
+
+/// Computes the linear convolution of `a` and `b` modulo `P` with a pair of NTTs.
+///
+/// The result has `a.len() + b.len() - 1` coefficients, or none if either input is empty.
+/// Panics if that is more than `2^MAX_LOG`, the longest NTT modulo `P`; see
+/// [`ModInt::MAX_LOG`].
+pub fn convolve<const P: u32>(a: &[ModInt<P>], b: &[ModInt<P>]) -> Vec<ModInt<P>> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+
+    let new_size = a.len() + b.len() - 1;
+    let new_aligned_size = new_size.next_power_of_two();
+    assert!(
+        new_aligned_size.trailing_zeros() <= ModInt::<P>::MAX_LOG,
+        "convolution length {} exceeds 2^{}, the longest NTT modulo {}",
+        new_size,
+        ModInt::<P>::MAX_LOG,
+        P
+    );
+
+    let mut a = a.to_owned();
+    let mut b = b.to_owned();
+    a.resize_with(new_aligned_size, Default::default);
+    b.resize_with(new_aligned_size, Default::default);
+
+    transform(&mut a, false);
+    transform(&mut b, false);
+
+    let mut new_points: Vec<ModInt<P>> = a.iter().zip(b.iter()).map(|(x, y)| *x * *y).collect();
+
+    transform(&mut new_points, true);
+    let n_inv = ModInt::<P>::from(new_aligned_size as u64).inv();
+    new_points.truncate(new_size);
+    new_points.iter_mut().for_each(|x| *x *= n_inv);
+    new_points
+}
+
+fn transform<const P: u32>(input: &mut [ModInt<P>], invert: bool) {
+    let n = input.len();
+
+    assert!(n.is_power_of_two());
+    let log = n.trailing_zeros();
+    if log == 0 {
+        return;
+    }
+
+    (0..n)
+        .map(|x| (x, x.reverse_bits() >> (usize::BITS - log)))
+        .filter(|(a, b)| a < b)
+        .for_each(|(i, j)| input.swap(i, j));
+
+    for stage in 1..=log {
+        let step = 1 << stage;
+        let mut first = ModInt::<P>::root_of_unity(stage);
+        if invert {
+            first = first.inv();
+        }
+
+        for i in (0..n).step_by(step) {
+            let mut num = ModInt::one();
+            for j in 0..step / 2 {
+                let a = input[i + j];
+                let b = num * input[i + j + step / 2];
+
+                input[i + j] = a + b;
+                input[i + j + step / 2] = a - b;
+                num *= first;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::M;
+
+    #[test]
+    fn primitive_root_search_finds_generators() {
+        // None of these are in `NTT_PRIMES`, so they go through the factor search.
+        for &(p, g) in &[(7, 3), (23, 5), (41, 6), (97, 5), (65_537, 3), (1_000_000_007, 5)] {
+            assert_eq!(primitive_root(p), g, "p = {}", p);
+        }
+        assert_eq!(ModInt::<97>::PRIMITIVE_ROOT, 5);
+    }
+
+    #[test]
+    fn sqrt_finds_every_residue() {
+        // `97 - 1 = 2^5 * 3` and `65537 - 1 = 2^16`, so Tonelli-Shanks needs several rounds.
+        fn check<const P: u32>() {
+            let mut residues = 0;
+            for x in 0..P.min(5000) {
+                let x = ModInt::<P>::from(x);
+                match x.sqrt() {
+                    Some(r) => {
+                        assert_eq!(r * r, x, "sqrt({}) mod {}", x, P);
+                        residues += 1;
+                    }
+                    None => assert_eq!(x.pow(((P - 1) / 2) as u64), -ModInt::one()),
+                }
+            }
+            assert!(residues > 1);
+        }
+
+        check::<97>();
+        check::<65_537>();
+        check::<998_244_353>();
+        check::<3_221_225_473>();
+    }
+
+    #[test]
+    fn ntt_evaluates_at_roots_of_unity_and_round_trips() {
+        for log in 0..6 {
+            let n = 1 << log;
+            let input: Vec<M> = (0..n as u64).map(|i| M::new(i * i * 7 + 3)).collect();
+            let values = ntt(&input);
+
+            let w = M::root_of_unity(log);
+            for (k, v) in values.iter().enumerate() {
+                let x = w.pow(k as u64);
+                let expected = input.iter().rev().fold(M::zero(), |acc, &c| acc * x + c);
+                assert_eq!(*v, expected, "n = {}, k = {}", n, k);
+            }
+            assert_eq!(inverse_ntt(&values), input);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "convolution length 33 exceeds 2^5")]
+    fn convolve_rejects_lengths_beyond_max_log() {
+        let a = vec![ModInt::<97>::one(); 17];
+        convolve(&a, &a);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_rejects_unreduced_values() {
+        use serde::de::value::{Error, U32Deserializer};
+        use serde::Deserialize;
+
+        let de = |value: u32| ModInt::<97>::deserialize(U32Deserializer::<Error>::new(value));
+        assert_eq!(de(0).unwrap(), ModInt::new(0));
+        assert_eq!(de(96).unwrap(), ModInt::new(96));
+        assert!(de(97).is_err());
+        assert!(de(u32::MAX).is_err());
+    }
+}
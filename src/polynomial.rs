@@ -0,0 +1,189 @@
+use crate::coefficient::Coefficient;
+use crate::crt::convolve_bigint;
+use crate::fft::{self, FftPlanner};
+use crate::ntt::ModInt;
+use num::bigint::BigInt;
+use num::complex::Complex;
+use num::{FromPrimitive, ToPrimitive, Zero};
+
+#[macro_use]
+mod ops;
+mod calculus;
+mod compose;
+mod division;
+mod format;
+mod gcd;
+mod multi;
+mod parse;
+mod roots;
+#[cfg(feature = "serde")]
+mod serialize;
+mod series;
+mod sparse;
+mod subproduct;
+
+pub use format::{FormatOptions, Notation, Order, PolynomialDisplay};
+pub use multi::MultiPolynomial;
+pub use parse::{ParseErrorKind, ParsePolynomialError};
+pub use roots::{Root, RootOptions};
+#[cfg(feature = "serde")]
+pub use serialize::serde_sparse;
+pub use sparse::SparsePolynomial;
+
+/// The largest exponent accepted from parsed or deserialized input. Polynomials are dense, so
+/// a single term such as `x^99999999999` would otherwise allocate every coefficient below it.
+pub const MAX_EXPONENT: usize = 1 << 24;
+
+/// A polynomial with coefficients stored in ascending order of exponent.
+///
+/// Trailing zero coefficients are stripped on construction, so the last stored
+/// coefficient is always the leading one.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Polynomial<T = Complex<f64>> {
+    coeffs: Vec<T>,
+}
+
+/// A polynomial with coefficients modulo the prime `P`, multiplied exactly.
+pub type ModPolynomial<const P: u32> = Polynomial<ModInt<P>>;
+
+impl<T: Coefficient> From<Vec<T>> for Polynomial<T> {
+    fn from(input: Vec<T>) -> Self {
+        Polynomial::new(input)
+    }
+}
+
+impl From<Vec<f64>> for Polynomial<Complex<f64>> {
+    fn from(input: Vec<f64>) -> Self {
+        Polynomial::new(input.into_iter().map(Complex::from).collect())
+    }
+}
+
+impl<const P: u32> From<Vec<i64>> for Polynomial<ModInt<P>> {
+    fn from(input: Vec<i64>) -> Self {
+        Polynomial::new(input.into_iter().map(ModInt::from).collect())
+    }
+}
+
+impl<T: Coefficient> Polynomial<T> {
+    /// Creates a polynomial from coefficients given in ascending order of exponent.
+    pub fn new(coeffs: Vec<T>) -> Self {
+        let mut ret = Polynomial { coeffs };
+        ret.trim();
+        ret
+    }
+
+    pub fn zero() -> Self {
+        Polynomial { coeffs: Vec::new() }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.coeffs.is_empty()
+    }
+
+    /// Returns the degree, or `None` for the zero polynomial.
+    pub fn degree(&self) -> Option<usize> {
+        self.coeffs.len().checked_sub(1)
+    }
+
+    /// Returns the coefficient of `x^exp`, which is zero past the degree.
+    pub fn coeff(&self, exp: usize) -> T {
+        self.coeffs.get(exp).cloned().unwrap_or_else(T::zero)
+    }
+
+    pub fn coeffs(&self) -> &[T] {
+        &self.coeffs
+    }
+
+    /// Iterates over the coefficients in ascending order of exponent.
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.coeffs.iter()
+    }
+
+    pub fn leading_coefficient(&self) -> Option<&T> {
+        self.coeffs.last()
+    }
+
+    pub fn into_coeffs(self) -> Vec<T> {
+        self.coeffs
+    }
+
+    fn trim(&mut self) {
+        while self.coeffs.last().is_some_and(Zero::is_zero) {
+            self.coeffs.pop();
+        }
+    }
+}
+
+impl Polynomial<f64> {
+    /// Same as `self * rhs`, taking the transforms from `planner` so that repeated products
+    /// of the same size reuse their twiddle tables.
+    pub fn mul_with_planner(&self, rhs: &Self, planner: &FftPlanner) -> Self {
+        Polynomial::new(fft::convolve_real_with(&self.coeffs, &rhs.coeffs, planner))
+    }
+}
+
+impl Polynomial<Complex<f64>> {
+    /// Same as `self * rhs`, taking the transforms from `planner` so that repeated products
+    /// of the same size reuse their twiddle tables.
+    pub fn mul_with_planner(&self, rhs: &Self, planner: &FftPlanner) -> Self {
+        Polynomial::new(fft::convolve_with(&self.coeffs, &rhs.coeffs, planner))
+    }
+
+    /// Multiplies two polynomials exactly with multi-prime NTT convolution.
+    ///
+    /// Every coefficient must be a Gaussian integer. The exact product is rounded to `f64`
+    /// only at the end, so unlike `*` it has no FFT rounding noise.
+    ///
+    /// Panics if the real or imaginary part of a coefficient is not a finite integer.
+    pub fn mul_exact(&self, rhs: &Self) -> Polynomial {
+        let parts = |p: &Polynomial| -> (Vec<BigInt>, Vec<BigInt>) {
+            let integer = |x: f64| {
+                assert!(x.fract() == 0., "coefficient {} is not an integer", x);
+                BigInt::from_f64(x).unwrap()
+            };
+            p.coeffs.iter().map(|c| (integer(c.re), integer(c.im))).unzip()
+        };
+        let (self_re, self_im) = parts(self);
+        let (rhs_re, rhs_im) = parts(rhs);
+
+        let mut re = convolve_bigint(&self_re, &rhs_re);
+        let mut im = convolve_bigint(&self_re, &rhs_im);
+        if self_im.iter().any(|x| !x.is_zero()) {
+            let im_im = convolve_bigint(&self_im, &rhs_im);
+            let im_re = convolve_bigint(&self_im, &rhs_re);
+            re.iter_mut().zip(im_im).for_each(|(x, y)| *x -= y);
+            im.iter_mut().zip(im_re).for_each(|(x, y)| *x += y);
+        }
+
+        Polynomial::new(
+            re.iter()
+                .zip(im.iter())
+                .map(|(x, y)| Complex::new(x.to_f64().unwrap(), y.to_f64().unwrap()))
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mul_exact_multiplies_gaussian_integers() {
+        let a = Polynomial::new(vec![Complex::new(3e12, 1.), Complex::new(-7., 2e12)]);
+        let b = Polynomial::new(vec![Complex::new(1., -1.), Complex::new(5., 0.)]);
+        let expected = Polynomial::new(vec![
+            Complex::new(3e12 + 1., 1. - 3e12),
+            Complex::new(15e12 - 7. + 2e12, 5. + 7. + 2e12),
+            Complex::new(-35., 10e12),
+        ]);
+        assert_eq!(a.mul_exact(&b), expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "not an integer")]
+    fn mul_exact_rejects_fractions() {
+        let a = Polynomial::new(vec![Complex::new(0.4, 0.)]);
+        a.mul_exact(&a);
+    }
+}
@@ -0,0 +1,181 @@
+use super::series::from_u64;
+use super::Polynomial;
+use crate::coefficient::{Coefficient, Field};
+
+impl<T: Coefficient> Polynomial<T> {
+    /// Returns the derivative.
+    pub fn derivative(&self) -> Self {
+        let mut exp = T::zero();
+        let coeffs = self
+            .coeffs
+            .iter()
+            .skip(1)
+            .map(|c| {
+                exp += T::one();
+                c.clone() * exp.clone()
+            })
+            .collect();
+
+        Polynomial::new(coeffs)
+    }
+
+    /// Returns `self(x + a)` by Horner's rule in `x + a`.
+    ///
+    /// This takes `O(n^2)`, but unlike the FFT convolution of [`shift`](Self::shift) it
+    /// keeps every coefficient accurate relative to its own size, so it is the better choice
+    /// for floating point polynomials of high degree or with a small shift.
+    pub fn shift_horner(&self, a: &T) -> Self {
+        let mut ret: Vec<T> = Vec::with_capacity(self.coeffs.len());
+        for c in self.coeffs.iter().rev() {
+            // Multiply by `x + a`, then add `c`.
+            ret.push(T::zero());
+            for i in (1..ret.len()).rev() {
+                let carry = ret[i - 1].clone();
+                ret[i] = carry + ret[i].clone() * a.clone();
+            }
+            ret[0] = ret[0].clone() * a.clone() + c.clone();
+        }
+
+        Polynomial::new(ret)
+    }
+}
+
+impl<T: Field> Polynomial<T> {
+    /// Returns the antiderivative whose constant term is `constant`.
+    ///
+    /// Panics for modular coefficients if an exponent is a multiple of the modulus.
+    pub fn integral(&self, constant: T) -> Self {
+        let mut exp = T::zero();
+        let coeffs = std::iter::once(constant)
+            .chain(self.coeffs.iter().map(|c| {
+                exp += T::one();
+                c.clone() / exp.clone()
+            }))
+            .collect();
+
+        Polynomial::new(coeffs)
+    }
+
+    /// Returns `self(x + a)`, the Taylor shift by `a`, with a single convolution: with
+    /// `b_i = p_i i!` and `c_j = a^j / j!`, the shifted coefficients are
+    /// `sum_i b_i c_(i - k) / k!`. Panics for modular coefficients if the degree reaches the
+    /// modulus, since the factorials vanish.
+    ///
+    /// Floating point types divide the factorials by `r^i` with `r` near `n / e`, which
+    /// cancels out but keeps them from overflowing. The error of the FFT product still scales
+    /// with its largest term rather than with each coefficient. In `f64`, for `1 <= |a| <= 2`
+    /// the result is accurate to about `1e-14` of its largest coefficient at degree 100 and
+    /// `1e-10` at degree 300, and other shifts lose accuracy faster: at degree 100 the error is
+    /// about `1e-9` for `a = 0.5`, `1e-5` for `a = 10` and `1e-2` for `a = 0.1`.
+    /// [`shift_horner`](Self::shift_horner) is accurate for every shift in `O(n^2)`.
+    pub fn shift(&self, a: &T) -> Self {
+        if self.coeffs.len() <= 1 {
+            return self.clone();
+        }
+
+        self.shift_convolution(a)
+    }
+
+    fn shift_convolution(&self, a: &T) -> Self {
+        let n = self.coeffs.len();
+        // `factorials[i]` is `i! / r^i`, and the powers of `r` cancel in `c_(i - k) / k!`.
+        let r = if T::EXACT {
+            T::one()
+        } else {
+            from_u64((n as f64 / std::f64::consts::E).round().max(1.) as u64)
+        };
+
+        let mut factorials = vec![T::one(); n];
+        let mut exp = T::zero();
+        for i in 1..n {
+            exp += T::one();
+            factorials[i] = factorials[i - 1].clone() * exp.clone() / r.clone();
+        }
+
+        let weighted: Vec<T> = self
+            .coeffs
+            .iter()
+            .zip(factorials.iter())
+            .rev()
+            .map(|(c, f)| c.clone() * f.clone())
+            .collect();
+
+        let mut powers = vec![T::one(); n];
+        let mut exp = T::zero();
+        for j in 1..n {
+            exp += T::one();
+            powers[j] = powers[j - 1].clone() * a.clone() * r.clone() / exp.clone();
+        }
+
+        let conv = T::convolve(&weighted, &powers);
+        let coeffs =
+            factorials.into_iter().enumerate().map(|(k, f)| conv[n - 1 - k].clone() / f).collect();
+
+        Polynomial::new(coeffs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::M;
+    use num::bigint::BigInt;
+    use num::rational::Ratio;
+    use num::ToPrimitive;
+
+    fn exact_shift(ints: &[i64], a: (i64, i64)) -> Vec<f64> {
+        // Integer shifts stay in `BigInt`, which is much faster than `Ratio`.
+        if a.1 == 1 {
+            let p = Polynomial::new(ints.iter().map(|&c| BigInt::from(c)).collect());
+            let shifted = p.shift_horner(&BigInt::from(a.0));
+            return shifted.iter().map(|c| c.to_f64().unwrap()).collect();
+        }
+
+        let p = Polynomial::new(ints.iter().map(|&c| Ratio::from(BigInt::from(c))).collect());
+        let a = Ratio::new(BigInt::from(a.0), BigInt::from(a.1));
+        p.shift_horner(&a).iter().map(|c| c.to_f64().unwrap()).collect()
+    }
+
+    fn ints(n: i64) -> Vec<i64> {
+        (0..n).map(|i| (i * 7 + 3) % 5 - 2).collect()
+    }
+
+    #[test]
+    fn float_shift_horner_matches_exact_rational_shift() {
+        let ints = ints(100);
+        let p = Polynomial::new(ints.iter().map(|&c| c as f64).collect());
+        let exact = exact_shift(&ints, (1, 2));
+
+        let shifted = p.shift_horner(&0.5);
+        assert_eq!(shifted.coeffs.len(), exact.len());
+        for (x, y) in shifted.coeffs.iter().zip(exact.iter()) {
+            assert!((x - y).abs() <= 1e-9 * y.abs().max(1.), "{} vs {}", x, y);
+        }
+        assert!((shifted.eval(&0.1) - p.eval(&0.6)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn float_shift_convolution_error_is_relative_to_the_largest_coefficient() {
+        // Past degree 170, where unscaled factorials would overflow.
+        let cases =
+            [(300, (2, 1), 1e-13), (300, (1, 1), 1e-9), (100, (-3, 1), 1e-11), (50, (1, 2), 1e-11)];
+        for &(n, a, tol) in &cases {
+            let ints = ints(n);
+            let p = Polynomial::new(ints.iter().map(|&c| c as f64).collect());
+            let exact = exact_shift(&ints, a);
+
+            let shifted = p.shift(&(a.0 as f64 / a.1 as f64));
+            let scale = exact.iter().fold(0., |acc: f64, y| acc.max(y.abs()));
+            for (k, y) in exact.iter().enumerate() {
+                let err = (shifted.coeff(k) - y).abs();
+                assert!(err <= tol * scale, "n = {}, a = {:?}, k = {}: {:e}", n, a, k, err / scale);
+            }
+        }
+    }
+
+    #[test]
+    fn mod_int_shift_matches_horner() {
+        let p = Polynomial::new((0..200u64).map(|i| M::new(i * i * 31 + 7)).collect());
+        assert_eq!(p.shift(&M::new(12345)), p.shift_horner(&M::new(12345)));
+    }
+}
@@ -0,0 +1,137 @@
+use super::Polynomial;
+use crate::coefficient::Coefficient;
+
+/// Below this many outer coefficients composition is done with Horner's rule.
+const COMPOSE_HORNER_THRESHOLD: usize = 16;
+
+impl<T: Coefficient> Polynomial<T> {
+    /// Returns `self(inner(x))`.
+    ///
+    /// Splits `self` into halves `lo + x^m hi` and recombines the composed halves as
+    /// `lo(inner) + inner^m hi(inner)`, with the powers `inner^(2^k)` found by repeated
+    /// squaring. Every level costs a few multiplications of the size of the result, for
+    /// `O(n log^2 n)` overall with fast multiplication.
+    ///
+    /// With floating point coefficients the FFT products have an error proportional to their
+    /// largest coefficient, so coefficients of the result much smaller than that can be lost
+    /// entirely.
+    pub fn compose(&self, inner: &Self) -> Self {
+        if inner.degree().unwrap_or(0) == 0 {
+            return Polynomial::new(vec![self.eval(&inner.coeff(0))]);
+        }
+
+        let mut powers = vec![inner.clone()];
+        while 1 << powers.len() < self.coeffs.len() {
+            let last = &powers[powers.len() - 1];
+            powers.push(last * last);
+        }
+        compose_split(&self.coeffs, inner, &powers)
+    }
+
+    /// Returns `self(inner(x)) mod x^n` with the baby-step giant-step algorithm of Brent and
+    /// Kung.
+    ///
+    /// With `k` about the square root of the length of `self`, the powers `inner^i` for
+    /// `i <= k` are computed once and each block of `k` coefficients becomes a linear
+    /// combination of them. The blocks are then combined by Horner's rule in `inner^k`, so
+    /// only about `2 sqrt(n)` truncated multiplications are needed. If `inner` has constant
+    /// term zero only the first `n` coefficients of `self` contribute.
+    pub fn compose_series(&self, inner: &Self, n: usize) -> Self {
+        let mut outer = &self.coeffs[..];
+        if inner.coeff(0).is_zero() {
+            outer = &outer[..outer.len().min(n)];
+        }
+        if n == 0 || outer.is_empty() {
+            return Polynomial::zero();
+        }
+
+        let k = (1..).find(|k| k * k >= outer.len()).unwrap_or(1);
+        let inner = truncated(inner.coeffs.clone(), n);
+        let mut powers = vec![truncated(vec![T::one()], n)];
+        for i in 0..k {
+            powers.push(truncated(T::convolve(&powers[i], &inner), n));
+        }
+
+        let mut ret = vec![T::zero(); n];
+        for block in outer.chunks(k).rev() {
+            ret = truncated(T::convolve(&ret, &powers[k]), n);
+            for (c, power) in block.iter().zip(powers.iter()) {
+                for (r, p) in ret.iter_mut().zip(power.iter()) {
+                    *r += c.clone() * p.clone();
+                }
+            }
+        }
+
+        Polynomial::new(ret)
+    }
+}
+
+/// Composes the polynomial with coefficients `coeffs` with `inner`, where `powers[k]` is
+/// `inner^(2^k)` and covers at least half the length.
+fn compose_split<T: Coefficient>(
+    coeffs: &[T],
+    inner: &Polynomial<T>,
+    powers: &[Polynomial<T>],
+) -> Polynomial<T> {
+    if coeffs.len() <= COMPOSE_HORNER_THRESHOLD {
+        return coeffs
+            .iter()
+            .rev()
+            .fold(Polynomial::zero(), |acc, c| &acc * inner + Polynomial::new(vec![c.clone()]));
+    }
+
+    let k = (coeffs.len().next_power_of_two() / 2).trailing_zeros() as usize;
+    let (lo, hi) = coeffs.split_at(1 << k);
+    compose_split(lo, inner, powers) + &compose_split(hi, inner, powers) * &powers[k]
+}
+
+fn truncated<T: Coefficient>(mut coeffs: Vec<T>, n: usize) -> Vec<T> {
+    coeffs.resize(n, T::zero());
+    coeffs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{random_poly, M};
+    use num::Zero;
+
+    /// A random inner polynomial of degree 5 with constant term zero.
+    fn random_without_constant(seed: u64) -> Polynomial<M> {
+        Polynomial::new([vec![M::zero()], random_poly::<M>(5, seed).coeffs].concat())
+    }
+
+    fn compose_horner(outer: &Polynomial<M>, inner: &Polynomial<M>) -> Polynomial<M> {
+        let step = |acc: Polynomial<M>, c: &M| &acc * inner + Polynomial::new(vec![*c]);
+        outer.iter().rev().fold(Polynomial::zero(), step)
+    }
+
+    #[test]
+    fn compose_matches_horner() {
+        // Longer than `COMPOSE_HORNER_THRESHOLD`, and not a power of two.
+        let outer: Polynomial<M> = random_poly(100, 1);
+        for inner in [random_poly(6, 2), random_without_constant(3)] {
+            assert_eq!(outer.compose(&inner), compose_horner(&outer, &inner));
+        }
+
+        let constant = Polynomial::new(vec![M::new(5)]);
+        assert_eq!(outer.compose(&constant), Polynomial::new(vec![outer.eval(&M::new(5))]));
+    }
+
+    #[test]
+    fn compose_series_matches_truncated_horner() {
+        let outer: Polynomial<M> = random_poly(100, 4);
+        let with_constant: Polynomial<M> = random_poly(6, 5);
+        let without_constant = random_without_constant(6);
+        assert!(!with_constant.coeff(0).is_zero());
+
+        for inner in [with_constant, without_constant] {
+            let full = compose_horner(&outer, &inner);
+            for &n in &[1, 17, 64, 150, 600] {
+                let expected = full.clone().truncate_series(n);
+                assert_eq!(outer.compose_series(&inner, n), expected, "n = {}", n);
+            }
+            assert!(outer.compose_series(&inner, 0).is_zero());
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use super::Polynomial;
+use crate::coefficient::Field;
+use std::ops::{Div, DivAssign, Rem, RemAssign};
+
+/// Below this divisor or quotient degree schoolbook division beats the Newton iteration.
+const NEWTON_THRESHOLD: usize = 64;
+
+impl<T: Field> Polynomial<T> {
+    /// Divides by `rhs`, returning the quotient and the remainder, whose degree is less than
+    /// that of `rhs`.
+    ///
+    /// Small inputs use schoolbook long division. Large ones multiply by the reciprocal of the
+    /// reversed divisor, computed with Newton iteration, so the cost is a few multiplications.
+    ///
+    /// Panics if `rhs` is the zero polynomial.
+    pub fn div_rem(&self, rhs: &Self) -> (Self, Self) {
+        let m = rhs.degree().expect("division by the zero polynomial");
+        let n = match self.degree() {
+            Some(n) if n >= m => n,
+            _ => return (Polynomial::zero(), self.clone()),
+        };
+
+        if m < NEWTON_THRESHOLD || n - m < NEWTON_THRESHOLD {
+            self.div_rem_schoolbook(rhs)
+        } else {
+            self.div_rem_newton(rhs)
+        }
+    }
+
+    fn div_rem_schoolbook(&self, rhs: &Self) -> (Self, Self) {
+        let m = rhs.coeffs.len() - 1;
+        let lead_inv = T::one() / rhs.coeffs[m].clone();
+
+        let mut rem = self.coeffs.clone();
+        let mut quot = vec![T::zero(); self.coeffs.len() - m];
+        for i in (0..quot.len()).rev() {
+            let c = rem[i + m].clone() * lead_inv.clone();
+            for (r, d) in rem[i..i + m].iter_mut().zip(rhs.coeffs.iter()) {
+                *r -= c.clone() * d.clone();
+            }
+            quot[i] = c;
+        }
+        rem.truncate(m);
+
+        (Polynomial::new(quot), Polynomial::new(rem))
+    }
+
+    /// With `rev(p) = x^deg(p) * p(1 / x)`, the quotient satisfies
+    /// `rev(q) = rev(self) / rev(rhs) mod x^(n - m + 1)`.
+    fn div_rem_newton(&self, rhs: &Self) -> (Self, Self) {
+        let m = rhs.coeffs.len() - 1;
+        let k = self.coeffs.len() - m;
+
+        let rev_self: Vec<T> = self.coeffs.iter().rev().take(k).cloned().collect();
+        let rev_rhs = Polynomial { coeffs: rhs.coeffs.iter().rev().cloned().collect() };
+
+        let mut rev_quot = T::convolve(&rev_self, &rev_rhs.inv_series(k).coeffs);
+        rev_quot.resize(k, T::zero());
+        rev_quot.reverse();
+        let quot = Polynomial::new(rev_quot);
+
+        let mut rem = self - &(&quot * rhs);
+        rem.coeffs.truncate(m);
+        rem.trim();
+
+        (quot, rem)
+    }
+}
+
+impl<T: Field> Div<&Polynomial<T>> for &Polynomial<T> {
+    type Output = Polynomial<T>;
+
+    fn div(self, rhs: &Polynomial<T>) -> Polynomial<T> {
+        self.div_rem(rhs).0
+    }
+}
+
+impl<T: Field> Rem<&Polynomial<T>> for &Polynomial<T> {
+    type Output = Polynomial<T>;
+
+    fn rem(self, rhs: &Polynomial<T>) -> Polynomial<T> {
+        self.div_rem(rhs).1
+    }
+}
+
+impl<T: Field> DivAssign<&Polynomial<T>> for Polynomial<T> {
+    fn div_assign(&mut self, rhs: &Polynomial<T>) {
+        *self = &*self / rhs;
+    }
+}
+
+impl<T: Field> RemAssign<&Polynomial<T>> for Polynomial<T> {
+    fn rem_assign(&mut self, rhs: &Polynomial<T>) {
+        *self = &*self % rhs;
+    }
+}
+
+forward_binop!(impl Div, div, Field);
+forward_binop!(impl Rem, rem, Field);
+
+forward_assign!(impl DivAssign, div_assign, Field);
+forward_assign!(impl RemAssign, rem_assign, Field);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{max_error, random_poly, random_vec, M};
+
+    // Both the divisor and the quotient degree reach `NEWTON_THRESHOLD`, so `div_rem` takes
+    // the Newton path.
+    const N: usize = 250;
+    const D: usize = 90;
+
+    #[test]
+    fn newton_matches_schoolbook_mod_int() {
+        let (a, b): (Polynomial<M>, _) = (random_poly(N + 1, 1), random_poly(D + 1, 2));
+
+        let (q, r) = a.div_rem(&b);
+        assert_eq!((q.clone(), r.clone()), a.div_rem_schoolbook(&b));
+        assert_eq!(&(&q * &b) + &r, a);
+        assert!(r.degree() < b.degree());
+    }
+
+    #[test]
+    fn newton_matches_schoolbook_f64() {
+        // A divisor whose leading coefficient dominates keeps the quotient well conditioned.
+        let a: Polynomial<f64> = random_poly(N + 1, 3);
+        let mut b: Vec<f64> = random_vec(D + 1, 4);
+        b[D] = 4.;
+        let b = Polynomial::new(b);
+
+        let (q, r) = a.div_rem_newton(&b);
+        let (q_school, r_school) = a.div_rem_schoolbook(&b);
+        assert!(max_error(&q.coeffs, &q_school.coeffs) < 1e-9);
+        assert!(max_error(&r.coeffs, &r_school.coeffs) < 1e-9);
+        assert!(r.degree() < b.degree());
+
+        assert!(max_error(&(&(&q * &b) + &r).coeffs, &a.coeffs) < 1e-9);
+    }
+}
@@ -0,0 +1,224 @@
+use super::Polynomial;
+use crate::coefficient::Coefficient;
+use std::fmt;
+
+/// How [`Polynomial::display_with`] renders a polynomial.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FormatOptions {
+    /// The name of the variable.
+    pub variable: String,
+    /// The order in which terms are written.
+    pub order: Order,
+    /// How powers and products are spelled.
+    pub notation: Notation,
+}
+
+/// The order of the terms of a formatted polynomial.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Order {
+    /// Highest power first.
+    Descending,
+    /// Constant term first.
+    Ascending,
+}
+
+/// The spelling of powers and products in a formatted polynomial.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Notation {
+    /// Superscript exponents, as in `3x² - x`.
+    Unicode,
+    /// Explicit operators, as in `3*x^2 - x`.
+    Ascii,
+    /// LaTeX math, as in `3x^{2} - x`.
+    Latex,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        FormatOptions {
+            variable: "x".to_string(),
+            order: Order::Descending,
+            notation: Notation::Unicode,
+        }
+    }
+}
+
+/// A [`Polynomial`] together with the [`FormatOptions`] to display it with, returned by
+/// [`Polynomial::display_with`].
+#[derive(Debug, Clone)]
+pub struct PolynomialDisplay<'a, T> {
+    poly: &'a Polynomial<T>,
+    options: FormatOptions,
+}
+
+impl<T: Coefficient> Polynomial<T> {
+    /// Returns an adapter that displays the polynomial according to `options`.
+    pub fn display_with(&self, options: FormatOptions) -> PolynomialDisplay<'_, T> {
+        PolynomialDisplay { poly: self, options }
+    }
+}
+
+impl<T: Coefficient + fmt::Display> fmt::Display for Polynomial<T> {
+    /// Formats the polynomial with the default [`FormatOptions`], as in `3x² - 2x + 1`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.display_with(FormatOptions::default()), f)
+    }
+}
+
+impl<T: Coefficient + fmt::Display> fmt::Display for PolynomialDisplay<'_, T> {
+    /// Writes the nonzero terms joined by ` + ` and ` - `, or `0` for the zero polynomial.
+    ///
+    /// Coefficients are printed with their own `Display`, using the precision of the
+    /// formatter if there is one, so by default nothing is lost. Unit coefficients are left
+    /// out, and coefficients that are not plain decimal numbers after taking out the sign,
+    /// such as complex numbers, are put in parentheses. The output parses back with
+    /// `FromStr`, except for LaTeX.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut terms: Vec<(usize, &T)> =
+            self.poly.coeffs.iter().enumerate().filter(|(_, c)| !c.is_zero()).collect();
+        if self.options.order == Order::Descending {
+            terms.reverse();
+        }
+        if terms.is_empty() {
+            return f.write_str("0");
+        }
+
+        for (i, (exp, c)) in terms.into_iter().enumerate() {
+            let text = match f.precision() {
+                Some(precision) => format!("{:.*}", precision, c),
+                None => c.to_string(),
+            };
+            // Unit coefficients are only written for the constant term.
+            let unit = exp > 0 && (*c == T::one() || *c == -T::one());
+            let (negative, magnitude) = match text.strip_prefix('-') {
+                _ if unit => (*c != T::one(), ""),
+                Some(rest) if !rest.contains(['+', '-']) => (true, rest),
+                _ => (false, &text[..]),
+            };
+
+            match (i, negative) {
+                (0, false) => {}
+                (0, true) => f.write_str("-")?,
+                (_, false) => f.write_str(" + ")?,
+                (_, true) => f.write_str(" - ")?,
+            }
+
+            if !unit {
+                if is_plain(magnitude) {
+                    f.write_str(magnitude)?;
+                } else {
+                    write!(f, "({})", magnitude)?;
+                }
+                if exp > 0 && self.options.notation == Notation::Ascii {
+                    f.write_str("*")?;
+                }
+            }
+
+            if exp > 0 {
+                f.write_str(&self.options.variable)?;
+            }
+            if exp > 1 {
+                match self.options.notation {
+                    Notation::Unicode => exp.to_string().chars().try_for_each(|d| {
+                        f.write_str(SUPERSCRIPTS[d.to_digit(10).unwrap_or(0) as usize])
+                    })?,
+                    Notation::Ascii => write!(f, "^{}", exp)?,
+                    Notation::Latex => write!(f, "^{{{}}}", exp)?,
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The superscript digits zero through nine.
+pub(super) const SUPERSCRIPTS: [&str; 10] = ["⁰", "¹", "²", "³", "⁴", "⁵", "⁶", "⁷", "⁸", "⁹"];
+
+/// Whether `s` is an unsigned decimal number, which needs no parentheses.
+fn is_plain(s: &str) -> bool {
+    !s.is_empty() && s.chars().all(|c| c.is_ascii_digit() || c == '.')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ntt::ModInt;
+    use num::complex::Complex;
+    use num::rational::Ratio;
+    use std::str::FromStr;
+
+    fn round_trip<T>(p: Polynomial<T>)
+    where
+        T: Coefficient + fmt::Display + FromStr,
+    {
+        let text = p.to_string();
+        assert_eq!(text.parse::<Polynomial<T>>(), Ok(p.clone()), "{}", text);
+
+        let options = FormatOptions { notation: Notation::Ascii, ..FormatOptions::default() };
+        let text = p.display_with(options).to_string();
+        assert_eq!(text.parse::<Polynomial<T>>(), Ok(p), "{}", text);
+    }
+
+    fn with(notation: Notation, order: Order) -> FormatOptions {
+        FormatOptions { variable: "t".to_string(), order, notation }
+    }
+
+    #[test]
+    fn display_round_trips() {
+        round_trip(Polynomial::new(vec![0.1, -1. / 3., 0., 1e-20, -1., 1e20, 1.]));
+        round_trip(Polynomial::new(vec![
+            Complex::new(-1., -2.),
+            Complex::new(0., -1.),
+            Complex::new(0.25, 0.),
+            Complex::new(-1., 0.),
+            Complex::new(1., 1e-17),
+        ]));
+        round_trip(Polynomial::new(vec![
+            Ratio::new(3i64, 4),
+            Ratio::new(-1, 3),
+            Ratio::from(-1),
+            Ratio::from(2),
+        ]));
+        round_trip(Polynomial::<ModInt<998244353>>::from(vec![998244352, 0, 1, 12345]));
+        round_trip(Polynomial::<f64>::zero());
+    }
+
+    #[test]
+    fn notations_and_orders() {
+        let p = Polynomial::new(vec![1., -1., 3., 0., 0., 0., 0., 0., 0., 0., 0., 2.]);
+        assert_eq!(p.to_string(), "2x¹¹ + 3x² - x + 1");
+        assert_eq!(
+            p.display_with(with(Notation::Ascii, Order::Descending)).to_string(),
+            "2*t^11 + 3*t^2 - t + 1"
+        );
+        assert_eq!(
+            p.display_with(with(Notation::Latex, Order::Descending)).to_string(),
+            "2t^{11} + 3t^{2} - t + 1"
+        );
+        assert_eq!(
+            p.display_with(with(Notation::Unicode, Order::Ascending)).to_string(),
+            "1 - t + 3t² + 2t¹¹"
+        );
+        assert_eq!(Polynomial::<f64>::zero().to_string(), "0");
+    }
+
+    #[test]
+    fn precision_is_passed_to_the_coefficients() {
+        let p = Polynomial::new(vec![1. / 3., -2., 0.125]);
+        assert_eq!(format!("{:.2}", p), "0.12x² - 2.00x + 0.33");
+        assert_eq!(p.to_string(), "0.125x² - 2x + 0.3333333333333333");
+
+        let c: Polynomial = Polynomial::new(vec![Complex::new(0.5, 1. / 3.), Complex::new(1., 0.)]);
+        assert_eq!(format!("{:.1}", c), "x + (0.5+0.3i)");
+    }
+
+    #[test]
+    fn unit_coefficients_are_left_out_except_in_the_constant_term() {
+        assert_eq!(Polynomial::new(vec![1., 1., -1.]).to_string(), "-x² + x + 1");
+        assert_eq!(Polynomial::new(vec![-1., -1.]).to_string(), "-x - 1");
+        assert_eq!(Polynomial::new(vec![0., 0., 1.]).to_string(), "x²");
+        let p = Polynomial::new(vec![-1., 1.]);
+        assert_eq!(p.display_with(with(Notation::Ascii, Order::Descending)).to_string(), "t - 1");
+    }
+}
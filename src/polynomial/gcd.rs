@@ -0,0 +1,337 @@
+use super::series::pow;
+use super::Polynomial;
+use crate::coefficient::{Field, Magnitude};
+
+/// From this degree on exact coefficient types reduce with half-GCD instead of one division
+/// at a time.
+const HALF_GCD_THRESHOLD: usize = 128;
+
+/// A 2x2 matrix mapping a pair of polynomials `(a, b)` to `(m00 a + m01 b, m10 a + m11 b)`.
+type Matrix<T> = [[Polynomial<T>; 2]; 2];
+
+impl<T: Field> Polynomial<T> {
+    /// Returns the monic greatest common divisor of `self` and `rhs`, or zero if both are
+    /// zero.
+    ///
+    /// Exact coefficient types use the half-GCD algorithm for large degrees, which needs
+    /// `O(n log^2 n)` with fast multiplication. Floating point remainders are hardly ever
+    /// exactly zero, so those should use [`gcd_with_tolerance`](Self::gcd_with_tolerance).
+    pub fn gcd(&self, rhs: &Self) -> Self {
+        self.extended_gcd(rhs).0
+    }
+
+    /// Returns `(g, s, t)` with `g` the [`gcd`](Self::gcd) of `self` and `rhs` and
+    /// `s * self + t * rhs = g`.
+    pub fn extended_gcd(&self, rhs: &Self) -> (Self, Self, Self) {
+        self.euclid(rhs, T::EXACT, |r, _| r)
+    }
+
+    /// Returns the resultant of `self` and `rhs`, the product of `rhs` over the roots of
+    /// `self` scaled by the leading coefficients, which is zero exactly when they share a
+    /// root. The resultant with the zero polynomial is zero.
+    ///
+    /// Follows the Euclidean remainder sequence, using
+    /// `res(a, b) = (-1)^(deg a deg b) lc(b)^(deg a - deg r) res(b, r)` for `r = a mod b`.
+    pub fn resultant(&self, rhs: &Self) -> T {
+        let (mut a, mut b) = (self.clone(), rhs.clone());
+        let mut ret = T::one();
+        loop {
+            let (n, m) = match (a.degree(), b.degree()) {
+                (Some(n), Some(m)) => (n, m),
+                _ => return T::zero(),
+            };
+            if m == 0 {
+                return ret * pow(b.coeffs[0].clone(), n as u64);
+            }
+
+            let r = &a % &b;
+            let k = match r.degree() {
+                Some(k) => k,
+                None => return T::zero(),
+            };
+            if n % 2 == 1 && m % 2 == 1 {
+                ret = -ret;
+            }
+            ret *= pow(b.coeffs[m].clone(), (n - k) as u64);
+
+            a = b;
+            b = r;
+        }
+    }
+
+    /// Runs the Euclidean algorithm, tracking the Bezout coefficients in a matrix and
+    /// passing each remainder through `trim` along with its dividend. With `fast` set, large
+    /// degrees skip ahead with [`half_gcd`].
+    fn euclid(
+        &self,
+        rhs: &Self,
+        fast: bool,
+        trim: impl Fn(Self, &Self) -> Self,
+    ) -> (Self, Self, Self) {
+        let mut m = identity();
+        let (mut a, mut b) = (self.clone(), rhs.clone());
+        while !b.is_zero() {
+            if fast && a.degree() >= Some(HALF_GCD_THRESHOLD) && a.degree() > b.degree() {
+                let step = half_gcd(&a, &b);
+                let (c, d) = apply(&step, &a, &b);
+                m = mul(&step, &m);
+                a = c;
+                b = d;
+                if b.is_zero() {
+                    break;
+                }
+            }
+
+            let (q, r) = a.div_rem(&b);
+            let r = trim(r, &a);
+            m = quotient_step(&q, m);
+            a = b;
+            b = r;
+        }
+
+        let [[s, t], _] = m;
+        match a.leading_coefficient() {
+            Some(lc) => {
+                let inv = T::one() / lc.clone();
+                (&a * inv.clone(), s * inv.clone(), t * inv)
+            }
+            None => (a, s, t),
+        }
+    }
+}
+
+impl<T: Field + Magnitude> Polynomial<T> {
+    /// Same as [`gcd`](Self::gcd), treating a remainder coefficient as zero when its
+    /// magnitude is at most `tol` times the largest coefficient magnitude of the dividend.
+    pub fn gcd_with_tolerance(&self, rhs: &Self, tol: f64) -> Self {
+        self.extended_gcd_with_tolerance(rhs, tol).0
+    }
+
+    /// Same as [`extended_gcd`](Self::extended_gcd), with the tolerance of
+    /// [`gcd_with_tolerance`](Self::gcd_with_tolerance).
+    pub fn extended_gcd_with_tolerance(&self, rhs: &Self, tol: f64) -> (Self, Self, Self) {
+        self.euclid(rhs, false, |r, a| {
+            let scale = a.iter().map(Magnitude::magnitude).fold(0., f64::max);
+            let mut coeffs = r.into_coeffs();
+            while coeffs.last().is_some_and(|c| c.magnitude() <= tol * scale) {
+                coeffs.pop();
+            }
+            Polynomial::new(coeffs)
+        })
+    }
+}
+
+/// Returns a matrix of quotient steps taking `(a, b)`, with `deg a > deg b`, to a pair
+/// whose first entry has degree at least `ceil(deg a / 2)` and whose second has less.
+///
+/// This is the recursion of Thull and Yap: half of the steps come from the top halves of
+/// the coefficients, one step is done directly and the rest come from the top halves of the
+/// resulting pair.
+fn half_gcd<T: Field>(a: &Polynomial<T>, b: &Polynomial<T>) -> Matrix<T> {
+    let n = a.degree().unwrap_or(0);
+    let m = n.div_ceil(2);
+    if !reaches(b, m) {
+        return identity();
+    }
+    if n < HALF_GCD_THRESHOLD {
+        return euclid_steps(a, b, m);
+    }
+
+    let r = half_gcd(&shift_down(a, m), &shift_down(b, m));
+    let (c, d) = apply(&r, a, b);
+    if !reaches(&d, m) {
+        return r;
+    }
+
+    let (q, e) = c.div_rem(&d);
+    let r = quotient_step(&q, r);
+    if !reaches(&e, m) {
+        return r;
+    }
+
+    let k = 2 * m - d.degree().unwrap_or(0);
+    mul(&half_gcd(&shift_down(&d, k), &shift_down(&e, k)), &r)
+}
+
+/// Performs single quotient steps on `(a, b)` until the degree of `b` drops below `m`.
+fn euclid_steps<T: Field>(a: &Polynomial<T>, b: &Polynomial<T>, m: usize) -> Matrix<T> {
+    let mut ret = identity();
+    let (mut a, mut b) = (a.clone(), b.clone());
+    while reaches(&b, m) {
+        let (q, r) = a.div_rem(&b);
+        ret = quotient_step(&q, ret);
+        a = b;
+        b = r;
+    }
+    ret
+}
+
+fn reaches<T: Field>(p: &Polynomial<T>, m: usize) -> bool {
+    p.degree().is_some_and(|d| d >= m)
+}
+
+/// Returns `p` divided by `x^k`, dropping the remainder.
+fn shift_down<T: Field>(p: &Polynomial<T>, k: usize) -> Polynomial<T> {
+    Polynomial::new(p.coeffs.get(k..).unwrap_or_default().to_vec())
+}
+
+fn identity<T: Field>() -> Matrix<T> {
+    let one = || Polynomial::new(vec![T::one()]);
+    [[one(), Polynomial::zero()], [Polynomial::zero(), one()]]
+}
+
+/// Returns `[[0, 1], [1, -q]] * m`, the step `(a, b) -> (b, a - q b)`.
+fn quotient_step<T: Field>(q: &Polynomial<T>, m: Matrix<T>) -> Matrix<T> {
+    let [[m00, m01], [m10, m11]] = m;
+    let r10 = &m00 - q * &m10;
+    let r11 = &m01 - q * &m11;
+    [[m10, m11], [r10, r11]]
+}
+
+fn apply<T: Field>(
+    m: &Matrix<T>,
+    a: &Polynomial<T>,
+    b: &Polynomial<T>,
+) -> (Polynomial<T>, Polynomial<T>) {
+    (&m[0][0] * a + &m[0][1] * b, &m[1][0] * a + &m[1][1] * b)
+}
+
+fn mul<T: Field>(x: &Matrix<T>, y: &Matrix<T>) -> Matrix<T> {
+    let entry = |i: usize, j: usize| &x[i][0] * &y[0][j] + &x[i][1] * &y[1][j];
+    [[entry(0, 0), entry(0, 1)], [entry(1, 0), entry(1, 1)]]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{random_vec, M};
+    use num::Zero;
+
+    /// A random monic polynomial of the given degree.
+    fn random(degree: usize, seed: u64) -> Polynomial<M> {
+        let mut coeffs: Vec<M> = random_vec(degree + 1, seed);
+        coeffs[degree] = M::new(1);
+        Polynomial::new(coeffs)
+    }
+
+    #[test]
+    fn half_gcd_matches_plain_euclid() {
+        for &(n, m, k) in &[(300, 200, 0), (600, 590, 150), (1000, 300, 400), (700, 700, 10)] {
+            let g = random(k, 1);
+            let a = &random(n, 2) * &g;
+            let b = &random(m, 3) * &g;
+
+            let (h, s, t) = a.extended_gcd(&b);
+            assert_eq!(h, g, "degrees {} {} {}", n, m, k);
+            assert_eq!(&(&s * &a) + &(&t * &b), h);
+            assert_eq!(a.euclid(&b, false, |r, _| r), (h, s, t));
+        }
+    }
+
+    #[test]
+    fn half_gcd_handles_degenerate_remainders() {
+        // Consecutive Fibonacci-like polynomials give a remainder sequence whose degrees drop
+        // by exactly one, the worst case for the matrix recursion.
+        let x = Polynomial::new(vec![M::new(0), M::new(1)]);
+        let (mut a, mut b) = (Polynomial::new(vec![M::new(1)]), x.clone());
+        for _ in 0..300 {
+            let c = &(&x * &b) + &a;
+            a = b;
+            b = c;
+        }
+
+        let (h, s, t) = b.extended_gcd(&a);
+        assert_eq!(h, Polynomial::new(vec![M::new(1)]));
+        assert_eq!(&(&s * &b) + &(&t * &a), h);
+        assert_eq!(b.gcd(&Polynomial::zero()), b);
+    }
+
+    /// Computes the determinant of the Sylvester matrix of `a` and `b` by Gaussian
+    /// elimination.
+    fn sylvester_determinant(a: &Polynomial<M>, b: &Polynomial<M>) -> M {
+        let (n, m) = (a.degree().unwrap(), b.degree().unwrap());
+        let mut rows: Vec<Vec<M>> = (0..m)
+            .map(|i| (0..n + m).map(|j| a.coeff((n + i).wrapping_sub(j))).collect())
+            .chain((0..n).map(|i| (0..n + m).map(|j| b.coeff((m + i).wrapping_sub(j))).collect()))
+            .collect();
+
+        let mut det = M::new(1);
+        for col in 0..n + m {
+            let pivot = match (col..n + m).find(|&r| !rows[r][col].is_zero()) {
+                Some(pivot) => pivot,
+                None => return M::new(0),
+            };
+            if pivot != col {
+                rows.swap(pivot, col);
+                det = -det;
+            }
+            det *= rows[col][col];
+            let inv = M::new(1) / rows[col][col];
+            let (done, rest) = rows.split_at_mut(col + 1);
+            for row in rest {
+                let factor = row[col] * inv;
+                for (x, &y) in row[col..].iter_mut().zip(&done[col][col..]) {
+                    *x -= factor * y;
+                }
+            }
+        }
+        det
+    }
+
+    #[test]
+    fn resultant_matches_sylvester_determinant() {
+        for (seed, &(n, m)) in
+            [(5, 3), (3, 5), (4, 4), (1, 6), (6, 1), (0, 4), (4, 0), (0, 0)].iter().enumerate()
+        {
+            let a = Polynomial::new(random_vec(n + 1, 2 * seed as u64));
+            let b = Polynomial::new(random_vec(m + 1, 2 * seed as u64 + 1));
+            assert_eq!((a.degree(), b.degree()), (Some(n), Some(m)));
+            assert_eq!(a.resultant(&b), sylvester_determinant(&a, &b), "degrees {} {}", n, m);
+        }
+    }
+
+    #[test]
+    fn resultant_of_known_polynomials() {
+        let p = |coeffs: &[i64]| Polynomial::<M>::from(coeffs.to_vec());
+
+        // `res(x^2 - 1, x - 2) = (1 - 2) (-1 - 2)`, and swapping has sign `(-1)^(2 * 1)`.
+        assert_eq!(p(&[-1, 0, 1]).resultant(&p(&[-2, 1])), M::new(3));
+        assert_eq!(p(&[-2, 1]).resultant(&p(&[-1, 0, 1])), M::new(3));
+        // `res(x - 1, x - 3) = 1 - 3`, and swapping has sign `(-1)^(1 * 1)`.
+        assert_eq!(p(&[-1, 1]).resultant(&p(&[-3, 1])), M::from(-2));
+        assert_eq!(p(&[-3, 1]).resultant(&p(&[-1, 1])), M::new(2));
+
+        // A constant `c` against a polynomial of degree `m` gives `c^m`.
+        assert_eq!(p(&[3]).resultant(&p(&[1, 2, 5])), M::new(9));
+        assert_eq!(p(&[1, 2, 5]).resultant(&p(&[3])), M::new(9));
+        assert_eq!(p(&[3]).resultant(&p(&[7])), M::new(1));
+
+        // A common root `x = 2`, or a zero operand, gives zero.
+        assert!(p(&[2, -3, 1]).resultant(&p(&[-10, 3, 1])).is_zero());
+        assert!(p(&[1, 1]).resultant(&Polynomial::zero()).is_zero());
+        assert!(Polynomial::zero().resultant(&p(&[1, 1])).is_zero());
+    }
+
+    #[test]
+    fn float_gcd_with_tolerance_recovers_the_common_factor() {
+        // `(x - 1) (x - 2)` times `x + 3` and `x - 5`.
+        let a: Polynomial<f64> = Polynomial::new(vec![6., -7., 0., 1.]);
+        let b = Polynomial::new(vec![-10., 17., -8., 1.]);
+        let common = [2., -3., 1.];
+
+        let (g, s, t) = a.extended_gcd_with_tolerance(&b, 1e-9);
+        assert_eq!(g.degree(), Some(2));
+        for (x, y) in g.iter().zip(&common) {
+            assert!((x - y).abs() < 1e-9, "{:?}", g);
+        }
+        let combination = &(&s * &a) + &(&t * &b);
+        for i in 0..3 {
+            assert!((combination.coeff(i) - g.coeff(i)).abs() < 1e-9, "{:?}", combination);
+        }
+        assert_eq!(a.gcd_with_tolerance(&b, 1e-9), g);
+
+        // Coprime inputs give a constant.
+        let c = Polynomial::new(vec![1., 0., 1.]);
+        assert_eq!(a.gcd_with_tolerance(&c, 1e-9), Polynomial::new(vec![1.]));
+    }
+}
@@ -0,0 +1,515 @@
+use super::Polynomial;
+use crate::coefficient::Coefficient;
+use num::complex::Complex;
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// A polynomial in the variables `x_0, ..., x_(k-1)`, stored densely.
+///
+/// The coefficients form a row-major array whose extent along each axis is one more than the
+/// degree in that variable, so the coefficient of `x_0^e_0 ... x_(k-1)^e_(k-1)` sits at the
+/// row-major index of `(e_0, ..., e_(k-1))`. The extents are trimmed to the actual degrees on
+/// construction, so equal polynomials have equal representations.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MultiPolynomial<T = Complex<f64>> {
+    shape: Vec<usize>,
+    coeffs: Vec<T>,
+}
+
+impl<T: Coefficient> MultiPolynomial<T> {
+    /// Creates a polynomial from its degree bounds in each variable and the row-major array
+    /// of its coefficients, which has `degrees[i] + 1` entries along axis `i`.
+    ///
+    /// Panics if the number of coefficients does not match the degree bounds.
+    pub fn new(degrees: &[usize], coeffs: Vec<T>) -> Self {
+        let shape: Vec<usize> = degrees.iter().map(|d| d + 1).collect();
+        assert_eq!(
+            coeffs.len(),
+            shape.iter().product::<usize>(),
+            "coefficient count does not match the degree bounds"
+        );
+        MultiPolynomial::with_shape(shape, coeffs)
+    }
+
+    /// Returns the zero polynomial in `vars` variables.
+    pub fn zero(vars: usize) -> Self {
+        MultiPolynomial { shape: vec![0; vars], coeffs: Vec::new() }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.coeffs.is_empty()
+    }
+
+    /// Returns the number of variables.
+    pub fn vars(&self) -> usize {
+        self.shape.len()
+    }
+
+    /// Returns the degree in `x_var`, or `None` for the zero polynomial.
+    pub fn degree(&self, var: usize) -> Option<usize> {
+        self.shape[var].checked_sub(1)
+    }
+
+    /// Returns the coefficient of `x_0^exps[0] ... x_(k-1)^exps[k-1]`, which is zero past the
+    /// degree in any variable.
+    ///
+    /// Panics if `exps` does not have one exponent per variable.
+    pub fn coeff(&self, exps: &[usize]) -> T {
+        assert_eq!(exps.len(), self.vars(), "expected one exponent per variable");
+        if exps.iter().zip(&self.shape).any(|(e, n)| e >= n) {
+            return T::zero();
+        }
+
+        let index = exps.iter().zip(&self.shape).fold(0, |acc, (e, n)| acc * n + e);
+        self.coeffs[index].clone()
+    }
+
+    /// Returns the coefficients in row-major order, with one more entry along each axis than
+    /// the degree in that variable.
+    pub fn coeffs(&self) -> &[T] {
+        &self.coeffs
+    }
+
+    pub fn into_coeffs(self) -> Vec<T> {
+        self.coeffs
+    }
+
+    /// Evaluates the polynomial at `point`, with Horner's rule along one axis at a time
+    /// starting from the last.
+    ///
+    /// Panics if `point` does not have one value per variable.
+    pub fn eval(&self, point: &[T]) -> T {
+        assert_eq!(point.len(), self.vars(), "expected one value per variable");
+        if self.is_zero() {
+            return T::zero();
+        }
+
+        let mut values = self.coeffs.clone();
+        for (x, &n) in point.iter().zip(&self.shape).rev() {
+            values = values
+                .chunks(n)
+                .map(|row| row.iter().rev().fold(T::zero(), |acc, c| acc * x.clone() + c.clone()))
+                .collect();
+        }
+        values.swap_remove(0)
+    }
+
+    /// Returns the partial derivative with respect to `x_var`.
+    pub fn partial_derivative(&self, var: usize) -> Self {
+        let n = self.shape[var];
+        if n <= 1 {
+            return MultiPolynomial::zero(self.vars());
+        }
+
+        let inner: usize = self.shape[var + 1..].iter().product();
+        let mut exp = T::zero();
+        let factors: Vec<T> = (1..n)
+            .map(|_| {
+                exp += T::one();
+                exp.clone()
+            })
+            .collect();
+
+        let mut coeffs = Vec::with_capacity(self.coeffs.len() / n * (n - 1));
+        for block in self.coeffs.chunks(n * inner) {
+            for (row, factor) in block.chunks(inner).skip(1).zip(&factors) {
+                coeffs.extend(row.iter().map(|c| c.clone() * factor.clone()));
+            }
+        }
+
+        let mut shape = self.shape.clone();
+        shape[var] -= 1;
+        MultiPolynomial::with_shape(shape, coeffs)
+    }
+
+    /// Splits the polynomial as `sum_j parts[j] x_0^j`, where each part is a polynomial in
+    /// the remaining variables `x_1, ..., x_(k-1)`, renumbered from zero.
+    ///
+    /// Panics for a polynomial in no variables.
+    pub fn parts(&self) -> Vec<Self> {
+        assert!(self.vars() > 0, "a polynomial in no variables has no parts");
+        if self.is_zero() {
+            return Vec::new();
+        }
+
+        let shape = &self.shape[1..];
+        let inner: usize = shape.iter().product();
+        self.coeffs
+            .chunks(inner)
+            .map(|row| MultiPolynomial::with_shape(shape.to_vec(), row.to_vec()))
+            .collect()
+    }
+
+    /// Builds `sum_j parts[j] x_0^j` in `vars` variables, where each part is a polynomial in
+    /// `x_1, ..., x_(vars-1)` numbered from zero. This is the inverse of [`parts`](Self::parts).
+    ///
+    /// Panics if `vars` is zero or some part is not in `vars - 1` variables.
+    pub fn from_parts(vars: usize, parts: Vec<Self>) -> Self {
+        assert!(vars > 0, "a polynomial in no variables has no parts");
+        assert!(
+            parts.iter().all(|p| p.vars() == vars - 1),
+            "every part must be in one variable fewer"
+        );
+
+        let mut inner = vec![0; vars - 1];
+        for part in &parts {
+            inner.iter_mut().zip(&part.shape).for_each(|(m, &n)| *m = (*m).max(n));
+        }
+
+        let mut coeffs = Vec::with_capacity(parts.len() * inner.iter().product::<usize>());
+        for part in &parts {
+            coeffs.extend(resize(&part.coeffs, &part.shape, &inner));
+        }
+
+        let shape = std::iter::once(parts.len()).chain(inner).collect();
+        MultiPolynomial::with_shape(shape, coeffs)
+    }
+
+    /// Returns the polynomial as a univariate [`Polynomial`], or `None` if it has more than
+    /// one variable.
+    pub fn to_polynomial(&self) -> Option<Polynomial<T>> {
+        if self.vars() > 1 {
+            return None;
+        }
+        Some(Polynomial::new(self.coeffs.clone()))
+    }
+
+    /// Splits a bivariate polynomial into the `nested[j](x_1)` with `sum_j nested[j] x_0^j`
+    /// equal to it, which is the inverse of the conversion from `Vec<Polynomial<T>>`. Returns
+    /// `None` unless the polynomial has exactly two variables.
+    pub fn to_nested(&self) -> Option<Vec<Polynomial<T>>> {
+        if self.vars() != 2 {
+            return None;
+        }
+        Some(self.parts().into_iter().map(|part| Polynomial::new(part.coeffs)).collect())
+    }
+
+    /// Creates a polynomial from a row-major array of the given shape, trimming the extents
+    /// down to the degrees.
+    fn with_shape(shape: Vec<usize>, coeffs: Vec<T>) -> Self {
+        let mut extents = vec![0; shape.len()];
+        let mut nonzero = false;
+        for (index, c) in coeffs.iter().enumerate() {
+            if c.is_zero() {
+                continue;
+            }
+            nonzero = true;
+            let mut rest = index;
+            for (extent, &n) in extents.iter_mut().zip(&shape).rev() {
+                *extent = (*extent).max(rest % n + 1);
+                rest /= n;
+            }
+        }
+
+        if !nonzero {
+            return MultiPolynomial::zero(shape.len());
+        }
+        if extents == shape {
+            return MultiPolynomial { shape, coeffs };
+        }
+
+        let coeffs = resize(&coeffs, &shape, &extents);
+        MultiPolynomial { shape: extents, coeffs }
+    }
+
+    /// Combines the coefficients of `self` and `rhs` pairwise over the union of their shapes,
+    /// treating missing entries as zero.
+    fn zip_with(&self, rhs: &Self, f: impl Fn(T, T) -> T) -> Self {
+        assert_eq!(self.vars(), rhs.vars(), "polynomials have different numbers of variables");
+
+        let shape: Vec<usize> =
+            self.shape.iter().zip(&rhs.shape).map(|(&m, &n)| m.max(n)).collect();
+        let a = resize(&self.coeffs, &self.shape, &shape);
+        let b = resize(&rhs.coeffs, &rhs.shape, &shape);
+        let coeffs = a.into_iter().zip(b).map(|(x, y)| f(x, y)).collect();
+        MultiPolynomial::with_shape(shape, coeffs)
+    }
+}
+
+impl<T: Coefficient> From<Polynomial<T>> for MultiPolynomial<T> {
+    fn from(poly: Polynomial<T>) -> Self {
+        MultiPolynomial { shape: vec![poly.coeffs.len()], coeffs: poly.coeffs }
+    }
+}
+
+/// Builds the bivariate polynomial `sum_j nested[j](x_1) x_0^j`, which
+/// [`to_nested`](MultiPolynomial::to_nested) splits back up.
+impl<T: Coefficient> From<Vec<Polynomial<T>>> for MultiPolynomial<T> {
+    fn from(nested: Vec<Polynomial<T>>) -> Self {
+        MultiPolynomial::from_parts(2, nested.into_iter().map(MultiPolynomial::from).collect())
+    }
+}
+
+impl<T: Coefficient> Add<&MultiPolynomial<T>> for &MultiPolynomial<T> {
+    type Output = MultiPolynomial<T>;
+
+    fn add(self, rhs: &MultiPolynomial<T>) -> MultiPolynomial<T> {
+        self.zip_with(rhs, |x, y| x + y)
+    }
+}
+
+impl<T: Coefficient> Sub<&MultiPolynomial<T>> for &MultiPolynomial<T> {
+    type Output = MultiPolynomial<T>;
+
+    fn sub(self, rhs: &MultiPolynomial<T>) -> MultiPolynomial<T> {
+        self.zip_with(rhs, |x, y| x - y)
+    }
+}
+
+impl<T: Coefficient> Mul<&MultiPolynomial<T>> for &MultiPolynomial<T> {
+    type Output = MultiPolynomial<T>;
+
+    /// Multiplies with Kronecker substitution: laying both operands out with the row-major
+    /// strides of the product's shape maps every monomial of the product to a distinct power
+    /// of a single variable, so one univariate product of `T` yields all the coefficients.
+    fn mul(self, rhs: &MultiPolynomial<T>) -> MultiPolynomial<T> {
+        assert_eq!(self.vars(), rhs.vars(), "polynomials have different numbers of variables");
+        if self.is_zero() || rhs.is_zero() {
+            return MultiPolynomial::zero(self.vars());
+        }
+
+        let shape: Vec<usize> = self.shape.iter().zip(&rhs.shape).map(|(m, n)| m + n - 1).collect();
+        let a = Polynomial::new(resize(&self.coeffs, &self.shape, &shape));
+        let b = Polynomial::new(resize(&rhs.coeffs, &rhs.shape, &shape));
+
+        let mut coeffs = (a * b).into_coeffs();
+        coeffs.resize(shape.iter().product(), T::zero());
+        MultiPolynomial::with_shape(shape, coeffs)
+    }
+}
+
+impl<T: Coefficient> Neg for MultiPolynomial<T> {
+    type Output = MultiPolynomial<T>;
+
+    fn neg(mut self) -> MultiPolynomial<T> {
+        self.coeffs.iter_mut().for_each(|c| *c = -c.clone());
+        self
+    }
+}
+
+impl<T: Coefficient> Neg for &MultiPolynomial<T> {
+    type Output = MultiPolynomial<T>;
+
+    fn neg(self) -> MultiPolynomial<T> {
+        -self.clone()
+    }
+}
+
+impl<T: Coefficient> Mul<T> for MultiPolynomial<T> {
+    type Output = MultiPolynomial<T>;
+
+    fn mul(mut self, rhs: T) -> MultiPolynomial<T> {
+        self.coeffs.iter_mut().for_each(|c| *c *= rhs.clone());
+        MultiPolynomial::with_shape(self.shape, self.coeffs)
+    }
+}
+
+impl<T: Coefficient> Mul<T> for &MultiPolynomial<T> {
+    type Output = MultiPolynomial<T>;
+
+    fn mul(self, rhs: T) -> MultiPolynomial<T> {
+        self.clone() * rhs
+    }
+}
+
+forward_owned_binop! {
+    MultiPolynomial;
+    impl Add, add, AddAssign, add_assign;
+    impl Sub, sub, SubAssign, sub_assign;
+    impl Mul, mul, MulAssign, mul_assign;
+}
+
+/// Copies a row-major array of shape `from` into a zero-filled row-major array of shape `to`,
+/// dropping the entries that fall outside `to`.
+fn resize<T: Coefficient>(coeffs: &[T], from: &[usize], to: &[usize]) -> Vec<T> {
+    let mut ret = vec![T::zero(); to.iter().product()];
+    let common: Vec<usize> = from.iter().zip(to).map(|(&m, &n)| m.min(n)).collect();
+    if coeffs.is_empty() || common.contains(&0) {
+        return ret;
+    }
+
+    // Copy one contiguous run along the last axis per index of the outer axes.
+    let (outer, run) = match common.split_last() {
+        Some((&run, outer)) => (outer, run),
+        None => (&[][..], 1),
+    };
+    let offset = |index: &[usize], shape: &[usize]| {
+        let row = index.iter().zip(shape).fold(0, |acc, (i, n)| acc * n + i);
+        row * shape.last().copied().unwrap_or(1)
+    };
+
+    let mut index = vec![0; outer.len()];
+    loop {
+        let (src, dst) = (offset(&index, from), offset(&index, to));
+        ret[dst..dst + run].clone_from_slice(&coeffs[src..src + run]);
+
+        // Advance the outer index like an odometer.
+        let mut axis = outer.len();
+        loop {
+            if axis == 0 {
+                return ret;
+            }
+            axis -= 1;
+            index[axis] += 1;
+            if index[axis] < outer[axis] {
+                break;
+            }
+            index[axis] = 0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{random_vec, M};
+
+    fn random(degrees: &[usize], seed: u64) -> MultiPolynomial<M> {
+        let len = degrees.iter().map(|d| d + 1).product();
+        MultiPolynomial::new(degrees, random_vec(len, seed))
+    }
+
+    fn check_mul_at_points(a_degrees: &[usize], b_degrees: &[usize], seed: u64) {
+        let a = random(a_degrees, seed);
+        let b = random(b_degrees, seed + 100);
+        let product = &a * &b;
+        for (i, (m, n)) in a_degrees.iter().zip(b_degrees).enumerate() {
+            assert_eq!(product.degree(i), Some(m + n));
+        }
+
+        for i in 0..10 {
+            let point: Vec<M> = random_vec(a_degrees.len(), seed + 200 + i);
+            assert_eq!(product.eval(&point), a.eval(&point) * b.eval(&point));
+        }
+    }
+
+    #[test]
+    fn mul_matches_eval_in_two_variables() {
+        check_mul_at_points(&[3, 5], &[4, 2], 1);
+        check_mul_at_points(&[0, 7], &[6, 0], 2);
+        check_mul_at_points(&[40, 30], &[25, 50], 3);
+    }
+
+    #[test]
+    fn mul_matches_eval_in_three_variables() {
+        check_mul_at_points(&[2, 3, 4], &[3, 1, 2], 4);
+        check_mul_at_points(&[0, 5, 1], &[2, 0, 6], 5);
+        check_mul_at_points(&[8, 6, 10], &[7, 9, 5], 6);
+    }
+
+    #[test]
+    fn mul_by_zero_is_zero() {
+        let a = random(&[2, 2], 7);
+        assert_eq!(&a * &MultiPolynomial::zero(2), MultiPolynomial::zero(2));
+    }
+
+    #[test]
+    fn construction_trims_extents_to_degrees() {
+        // `1 + 2 x_1 + 3 x_0 x_1` padded to degree bounds `[2, 3]`.
+        let coeffs = vec![1, 2, 0, 0, 0, 3, 0, 0, 0, 0, 0, 0];
+        let p = MultiPolynomial::new(&[2, 3], coeffs);
+        assert_eq!((p.degree(0), p.degree(1)), (Some(1), Some(1)));
+        assert_eq!(p.coeffs(), &[1, 2, 0, 3]);
+        assert_eq!(p, MultiPolynomial::new(&[1, 1], vec![1, 2, 0, 3]));
+        assert_eq!(p.coeff(&[1, 1]), 3);
+        assert_eq!(p.coeff(&[2, 0]), 0);
+
+        let zero = MultiPolynomial::new(&[1, 2], vec![0; 6]);
+        assert!(zero.is_zero());
+        assert_eq!(zero, MultiPolynomial::zero(2));
+        assert_eq!(zero.degree(0), None);
+    }
+
+    #[test]
+    fn add_and_sub_resize_and_trim() {
+        // `(x_0^2 + x_1) - (x_0^2 - x_1^3)` leaves `x_1 + x_1^3`.
+        let a = MultiPolynomial::new(&[2, 1], vec![0, 1, 0, 0, 1, 0]);
+        let b = MultiPolynomial::new(&[2, 3], vec![0, 0, 0, -1, 0, 0, 0, 0, 1, 0, 0, 0]);
+        let diff = &a - &b;
+        assert_eq!(diff, MultiPolynomial::new(&[0, 3], vec![0, 1, 0, 1]));
+        let sum = MultiPolynomial::new(&[2, 3], vec![0, 1, 0, -1, 0, 0, 0, 0, 2, 0, 0, 0]);
+        assert_eq!(&a + &b, sum);
+        assert!((&a - &a).is_zero());
+    }
+
+    #[test]
+    fn partial_derivative_in_each_variable() {
+        // `p = 7 + 5 x_0 x_1^2 + 3 x_0^2 x_1`.
+        let p = MultiPolynomial::new(&[2, 2], vec![7, 0, 0, 0, 0, 5, 0, 3, 0]);
+        // `6 x_0 x_1 + 5 x_1^2`
+        assert_eq!(p.partial_derivative(0), MultiPolynomial::new(&[1, 2], vec![0, 0, 5, 0, 6, 0]));
+        // `10 x_0 x_1 + 3 x_0^2`
+        assert_eq!(p.partial_derivative(1), MultiPolynomial::new(&[2, 1], vec![0, 0, 0, 10, 3, 0]));
+
+        let q = MultiPolynomial::new(&[0, 3], vec![1, 2, 3, 4]);
+        assert!(q.partial_derivative(0).is_zero());
+        assert!(MultiPolynomial::<i64>::zero(3).partial_derivative(1).is_zero());
+
+        // The middle axis of a trivariate polynomial.
+        let r = random(&[2, 3, 2], 8);
+        let dr = r.partial_derivative(1);
+        for e0 in 0..3 {
+            for e1 in 0..3 {
+                for e2 in 0..3 {
+                    let expected = r.coeff(&[e0, e1 + 1, e2]) * M::new(e1 as u64 + 1);
+                    assert_eq!(dr.coeff(&[e0, e1, e2]), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn parts_round_trip() {
+        for (seed, degrees) in [[3, 2, 4], [0, 5, 1], [4, 0, 0]].iter().enumerate() {
+            let p = random(degrees, seed as u64 + 9);
+            let parts = p.parts();
+            assert_eq!(parts.len(), degrees[0] + 1);
+            for (j, part) in parts.iter().enumerate() {
+                assert_eq!(part.vars(), 2);
+                assert_eq!(part.coeff(&[1, 0]), p.coeff(&[j, 1, 0]));
+            }
+            assert_eq!(MultiPolynomial::from_parts(3, parts), p);
+        }
+        assert!(MultiPolynomial::<i64>::zero(2).parts().is_empty());
+    }
+
+    #[test]
+    fn from_parts_pads_to_the_widest_part() {
+        // `(1 + x_0^2) + x_1 (x_0^3) + x_1^2 (0)`, with a trailing zero part to drop.
+        let parts = vec![
+            MultiPolynomial::new(&[2], vec![1, 0, 1]),
+            MultiPolynomial::new(&[3], vec![0, 0, 0, 1]),
+            MultiPolynomial::zero(1),
+        ];
+        let p = MultiPolynomial::from_parts(2, parts);
+        assert_eq!((p.degree(0), p.degree(1)), (Some(1), Some(3)));
+        assert_eq!(p.coeffs(), &[1, 0, 1, 0, 0, 0, 0, 1]);
+    }
+
+    #[test]
+    fn from_nested_polynomials() {
+        let nested =
+            vec![Polynomial::new(vec![1, 2]), Polynomial::zero(), Polynomial::new(vec![0, 0, 3])];
+        let p = MultiPolynomial::from(nested);
+        assert_eq!(p.vars(), 2);
+        assert_eq!((p.degree(0), p.degree(1)), (Some(2), Some(2)));
+        assert_eq!(p.coeffs(), &[1, 2, 0, 0, 0, 0, 0, 0, 3]);
+        assert_eq!(p.eval(&[2, 10]), 1 + 20 + 3 * 4 * 100);
+        let zero = MultiPolynomial::from(vec![Polynomial::<i64>::zero()]);
+        assert_eq!(zero, MultiPolynomial::zero(2));
+    }
+
+    #[test]
+    fn nested_polynomials_round_trip() {
+        let nested =
+            vec![Polynomial::new(vec![1, 2]), Polynomial::zero(), Polynomial::new(vec![0, 0, 3])];
+        let p = MultiPolynomial::from(nested.clone());
+        assert_eq!(p.to_nested(), Some(nested));
+
+        let q = random(&[4, 6], 10);
+        assert_eq!(MultiPolynomial::from(q.to_nested().unwrap()), q);
+
+        assert_eq!(MultiPolynomial::<i64>::zero(2).to_nested(), Some(Vec::new()));
+        assert_eq!(MultiPolynomial::from(Polynomial::new(vec![1, 2])).to_nested(), None);
+        assert_eq!(random(&[1, 1, 1], 11).to_nested(), None);
+    }
+}
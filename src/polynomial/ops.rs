@@ -0,0 +1,371 @@
+use super::Polynomial;
+use crate::coefficient::Coefficient;
+use num::complex::Complex;
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+macro_rules! forward_binop {
+    (impl $imp:ident, $method:ident) => {
+        forward_binop!(impl $imp, $method, Coefficient);
+    };
+    (impl $imp:ident, $method:ident, $bound:path) => {
+        impl<T: $bound> $imp<Polynomial<T>> for Polynomial<T> {
+            type Output = Polynomial<T>;
+
+            fn $method(self, rhs: Polynomial<T>) -> Polynomial<T> {
+                (&self).$method(&rhs)
+            }
+        }
+
+        impl<T: $bound> $imp<&Polynomial<T>> for Polynomial<T> {
+            type Output = Polynomial<T>;
+
+            fn $method(self, rhs: &Polynomial<T>) -> Polynomial<T> {
+                (&self).$method(rhs)
+            }
+        }
+
+        impl<T: $bound> $imp<Polynomial<T>> for &Polynomial<T> {
+            type Output = Polynomial<T>;
+
+            fn $method(self, rhs: Polynomial<T>) -> Polynomial<T> {
+                self.$method(&rhs)
+            }
+        }
+    };
+}
+
+macro_rules! forward_assign {
+    (impl $imp:ident, $method:ident) => {
+        forward_assign!(impl $imp, $method, Coefficient);
+    };
+    (impl $imp:ident, $method:ident, $bound:path) => {
+        impl<T: $bound> $imp<Polynomial<T>> for Polynomial<T> {
+            fn $method(&mut self, rhs: Polynomial<T>) {
+                self.$method(&rhs);
+            }
+        }
+    };
+}
+
+macro_rules! forward_owned_binop {
+    ($ty:ident; $(impl $imp:ident, $method:ident, $assign_imp:ident, $assign_method:ident;)*) => {
+        $(
+            impl<T: Coefficient> $imp<$ty<T>> for $ty<T> {
+                type Output = $ty<T>;
+
+                fn $method(self, rhs: $ty<T>) -> $ty<T> {
+                    (&self).$method(&rhs)
+                }
+            }
+
+            impl<T: Coefficient> $imp<&$ty<T>> for $ty<T> {
+                type Output = $ty<T>;
+
+                fn $method(self, rhs: &$ty<T>) -> $ty<T> {
+                    (&self).$method(rhs)
+                }
+            }
+
+            impl<T: Coefficient> $imp<$ty<T>> for &$ty<T> {
+                type Output = $ty<T>;
+
+                fn $method(self, rhs: $ty<T>) -> $ty<T> {
+                    self.$method(&rhs)
+                }
+            }
+
+            impl<T: Coefficient> $assign_imp<&$ty<T>> for $ty<T> {
+                fn $assign_method(&mut self, rhs: &$ty<T>) {
+                    *self = (&*self).$method(rhs);
+                }
+            }
+
+            impl<T: Coefficient> $assign_imp<$ty<T>> for $ty<T> {
+                fn $assign_method(&mut self, rhs: $ty<T>) {
+                    *self = (&*self).$method(&rhs);
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! scalar_lhs_ops {
+    ($($scalar:ty => $coeff:ty),*) => {
+        $(
+            impl Mul<&Polynomial<$coeff>> for $scalar {
+                type Output = Polynomial<$coeff>;
+
+                fn mul(self, rhs: &Polynomial<$coeff>) -> Polynomial<$coeff> {
+                    rhs * <$coeff>::from(self)
+                }
+            }
+
+            impl Mul<Polynomial<$coeff>> for $scalar {
+                type Output = Polynomial<$coeff>;
+
+                fn mul(self, rhs: Polynomial<$coeff>) -> Polynomial<$coeff> {
+                    rhs * <$coeff>::from(self)
+                }
+            }
+        )*
+    };
+}
+
+impl<T: Coefficient> AddAssign<&Polynomial<T>> for Polynomial<T> {
+    fn add_assign(&mut self, rhs: &Polynomial<T>) {
+        if self.coeffs.len() < rhs.coeffs.len() {
+            self.coeffs.resize_with(rhs.coeffs.len(), T::zero);
+        }
+
+        self.coeffs.iter_mut().zip(rhs.coeffs.iter()).for_each(|(x, y)| *x += y.clone());
+        self.trim();
+    }
+}
+
+impl<T: Coefficient> SubAssign<&Polynomial<T>> for Polynomial<T> {
+    fn sub_assign(&mut self, rhs: &Polynomial<T>) {
+        if self.coeffs.len() < rhs.coeffs.len() {
+            self.coeffs.resize_with(rhs.coeffs.len(), T::zero);
+        }
+
+        self.coeffs.iter_mut().zip(rhs.coeffs.iter()).for_each(|(x, y)| *x -= y.clone());
+        self.trim();
+    }
+}
+
+impl<T: Coefficient> MulAssign<&Polynomial<T>> for Polynomial<T> {
+    fn mul_assign(&mut self, rhs: &Polynomial<T>) {
+        *self = &*self * rhs;
+    }
+}
+
+impl<T: Coefficient> Add<&Polynomial<T>> for &Polynomial<T> {
+    type Output = Polynomial<T>;
+
+    fn add(self, rhs: &Polynomial<T>) -> Polynomial<T> {
+        let mut ret = self.clone();
+        ret += rhs;
+        ret
+    }
+}
+
+impl<T: Coefficient> Sub<&Polynomial<T>> for &Polynomial<T> {
+    type Output = Polynomial<T>;
+
+    fn sub(self, rhs: &Polynomial<T>) -> Polynomial<T> {
+        let mut ret = self.clone();
+        ret -= rhs;
+        ret
+    }
+}
+
+impl<T: Coefficient> Mul<&Polynomial<T>> for &Polynomial<T> {
+    type Output = Polynomial<T>;
+
+    /// Multiplies with the backend of `T`, as chosen by [`Coefficient::convolve`].
+    ///
+    /// # Panics
+    ///
+    /// Integer coefficients must fit the product: with `i64` this panics if a coefficient of
+    /// the result overflows `i64`.
+    fn mul(self, rhs: &Polynomial<T>) -> Polynomial<T> {
+        Polynomial::new(T::convolve(&self.coeffs, &rhs.coeffs))
+    }
+}
+
+impl<T: Coefficient> Neg for Polynomial<T> {
+    type Output = Polynomial<T>;
+
+    fn neg(mut self) -> Polynomial<T> {
+        self.coeffs.iter_mut().for_each(|c| *c = -c.clone());
+        self
+    }
+}
+
+impl<T: Coefficient> Neg for &Polynomial<T> {
+    type Output = Polynomial<T>;
+
+    fn neg(self) -> Polynomial<T> {
+        -self.clone()
+    }
+}
+
+impl<T: Coefficient> Mul<T> for &Polynomial<T> {
+    type Output = Polynomial<T>;
+
+    fn mul(self, rhs: T) -> Polynomial<T> {
+        let mut ret = self.clone();
+        ret *= rhs;
+        ret
+    }
+}
+
+impl<T: Coefficient> Mul<T> for Polynomial<T> {
+    type Output = Polynomial<T>;
+
+    fn mul(mut self, rhs: T) -> Polynomial<T> {
+        self *= rhs;
+        self
+    }
+}
+
+impl<T: Coefficient> MulAssign<T> for Polynomial<T> {
+    fn mul_assign(&mut self, rhs: T) {
+        self.coeffs.iter_mut().for_each(|c| *c *= rhs.clone());
+        self.trim();
+    }
+}
+
+impl Mul<f64> for &Polynomial<Complex<f64>> {
+    type Output = Polynomial<Complex<f64>>;
+
+    fn mul(self, rhs: f64) -> Polynomial<Complex<f64>> {
+        self * Complex::from(rhs)
+    }
+}
+
+impl Mul<f64> for Polynomial<Complex<f64>> {
+    type Output = Polynomial<Complex<f64>>;
+
+    fn mul(self, rhs: f64) -> Polynomial<Complex<f64>> {
+        self * Complex::from(rhs)
+    }
+}
+
+impl MulAssign<f64> for Polynomial<Complex<f64>> {
+    fn mul_assign(&mut self, rhs: f64) {
+        *self *= Complex::from(rhs);
+    }
+}
+
+forward_binop!(impl Add, add);
+forward_binop!(impl Sub, sub);
+forward_binop!(impl Mul, mul);
+
+forward_assign!(impl AddAssign, add_assign);
+forward_assign!(impl SubAssign, sub_assign);
+forward_assign!(impl MulAssign, mul_assign);
+
+scalar_lhs_ops!(
+    f64 => f64,
+    f32 => f32,
+    f64 => Complex<f64>,
+    Complex<f64> => Complex<f64>,
+    Complex<f32> => Complex<f32>
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::max_error;
+
+    fn poly(coeffs: &[f64]) -> Polynomial {
+        Polynomial::from(coeffs.to_vec())
+    }
+
+    fn assert_close(a: &Polynomial, b: &Polynomial) {
+        assert!(max_error(a.coeffs(), b.coeffs()) < 1e-9, "{:?} != {:?}", a, b);
+    }
+
+    /// Returns `1 + 2x + 3x^2`, `-1 + 4x^3`, and their sum, difference and product.
+    fn operands() -> [Polynomial; 5] {
+        [
+            poly(&[1., 2., 3.]),
+            poly(&[-1., 0., 0., 4.]),
+            poly(&[0., 2., 3., 4.]),
+            poly(&[2., 2., 3., -4.]),
+            poly(&[-1., -2., -3., 4., 8., 12.]),
+        ]
+    }
+
+    #[test]
+    fn owned_and_borrowed_operands_agree() {
+        let [a, b, sum, diff, product] = operands();
+
+        assert_eq!(&a + &b, sum);
+        assert_eq!(a.clone() + b.clone(), sum);
+        assert_eq!(a.clone() + &b, sum);
+        assert_eq!(&a + b.clone(), sum);
+
+        assert_eq!(&a - &b, diff);
+        assert_eq!(a.clone() - b.clone(), diff);
+        assert_eq!(a.clone() - &b, diff);
+        assert_eq!(&a - b.clone(), diff);
+
+        assert_close(&(&a * &b), &product);
+        assert_close(&(a.clone() * b.clone()), &product);
+        assert_close(&(a.clone() * &b), &product);
+        assert_close(&(&a * b.clone()), &product);
+
+        assert_eq!(-&a, poly(&[-1., -2., -3.]));
+        assert_eq!(-a.clone(), poly(&[-1., -2., -3.]));
+    }
+
+    #[test]
+    fn assign_operators_match_binary_ones() {
+        let [a, b, sum, _, product] = operands();
+
+        let mut x = a.clone();
+        x += &b;
+        assert_eq!(x, sum);
+        x -= b.clone();
+        assert_eq!(x, a);
+        x += b.clone();
+        x -= &b;
+        assert_eq!(x, a);
+
+        x *= &b;
+        assert_close(&x, &product);
+        let mut y = a.clone();
+        y *= b.clone();
+        assert_close(&y, &product);
+    }
+
+    #[test]
+    fn mul_leaves_both_operands_unchanged() {
+        let [a, b, _, _, product] = operands();
+        let (a_before, b_before) = (a.clone(), b.clone());
+
+        let c = poly(&[5.]);
+        let chained = &a * &b + &c;
+        assert_eq!(a, a_before);
+        assert_eq!(b, b_before);
+        assert_close(&chained, &(&product + &c));
+    }
+
+    #[test]
+    fn scalar_multiplication() {
+        let [a, ..] = operands();
+        let doubled = poly(&[2., 4., 6.]);
+        assert_eq!(&a * 2., doubled);
+        assert_eq!(a.clone() * 2., doubled);
+        assert_eq!(2. * &a, doubled);
+        assert_eq!(2. * a.clone(), doubled);
+
+        let i = Complex::new(0., 1.);
+        let rotated = Polynomial::new(vec![i, 2. * i, 3. * i]);
+        assert_eq!(&a * i, rotated);
+        assert_eq!(a.clone() * i, rotated);
+        assert_eq!(i * &a, rotated);
+        assert_eq!(i * a.clone(), rotated);
+
+        let mut x = a.clone();
+        x *= 2.;
+        x *= i;
+        assert_eq!(x, &doubled * i);
+    }
+
+    #[test]
+    fn results_are_trimmed() {
+        let [a, ..] = operands();
+        assert!((&a - &a).is_zero());
+        assert!((a.clone() * 0.).is_zero());
+        assert!((&a * Complex::new(0., 0.)).is_zero());
+        assert!((&a * &Polynomial::zero()).is_zero());
+        assert_eq!((&a - &poly(&[0., 0., 3.])).degree(), Some(1));
+
+        let mut x = a.clone();
+        x -= &a;
+        assert_eq!(x, Polynomial::zero());
+    }
+}
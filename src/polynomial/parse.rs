@@ -0,0 +1,337 @@
+use super::format::SUPERSCRIPTS;
+use super::{Polynomial, MAX_EXPONENT};
+use crate::coefficient::Coefficient;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// The error returned when parsing a [`Polynomial`] fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsePolynomialError {
+    kind: ParseErrorKind,
+    position: usize,
+}
+
+/// The reason a [`Polynomial`] could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParseErrorKind {
+    /// The input contains no terms.
+    Empty,
+    /// A character that cannot start or continue a term.
+    UnexpectedChar(char),
+    /// The input ends in the middle of a term.
+    UnexpectedEnd,
+    /// A coefficient the coefficient type does not accept.
+    InvalidCoefficient,
+    /// An exponent that is not a nonnegative integer of at most [`MAX_EXPONENT`].
+    InvalidExponent,
+    /// A variable named differently from the first one.
+    MismatchedVariable,
+    /// A parenthesized coefficient without its closing parenthesis.
+    UnclosedParenthesis,
+}
+
+impl ParsePolynomialError {
+    /// Returns what went wrong.
+    pub fn kind(&self) -> &ParseErrorKind {
+        &self.kind
+    }
+
+    /// Returns the byte offset into the input at which the error was found.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+impl fmt::Display for ParsePolynomialError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            ParseErrorKind::Empty => write!(f, "empty polynomial")?,
+            ParseErrorKind::UnexpectedChar(c) => write!(f, "unexpected character {:?}", c)?,
+            ParseErrorKind::UnexpectedEnd => write!(f, "unexpected end of input")?,
+            ParseErrorKind::InvalidCoefficient => write!(f, "invalid coefficient")?,
+            ParseErrorKind::InvalidExponent => write!(f, "invalid exponent")?,
+            ParseErrorKind::MismatchedVariable => write!(f, "mismatched variable")?,
+            ParseErrorKind::UnclosedParenthesis => write!(f, "unclosed parenthesis")?,
+        }
+        write!(f, " at position {}", self.position)
+    }
+}
+
+impl Error for ParsePolynomialError {}
+
+impl<T: Coefficient + FromStr> FromStr for Polynomial<T> {
+    type Err = ParsePolynomialError;
+
+    /// Parses a sum of terms such as `3x^2 - 2x + 1`.
+    ///
+    /// A term is a coefficient, a variable with an optional `^exponent` or superscript
+    /// exponent, or both, optionally joined by `*`. Coefficients are parsed with the
+    /// [`FromStr`] impl of `T`: plain numbers may carry an `i` suffix for imaginary parts,
+    /// and anything else, such as `(1+2i)` or `(3/4)`, goes in parentheses. The variable can
+    /// have any name, as long as it is the same in every term. Repeated exponents are added up
+    /// and whitespace between tokens is ignored, so the output of `Display` parses back.
+    ///
+    /// Exponents above [`MAX_EXPONENT`] are rejected with [`ParseErrorKind::InvalidExponent`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Parser { input: s, pos: 0, variable: None }.parse()
+    }
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+    variable: Option<&'a str>,
+}
+
+impl<'a> Parser<'a> {
+    fn parse<T: Coefficient + FromStr>(mut self) -> Result<Polynomial<T>, ParsePolynomialError> {
+        let mut coeffs = Vec::new();
+        self.skip_whitespace();
+        if self.peek().is_none() {
+            return Err(self.error(ParseErrorKind::Empty));
+        }
+
+        while self.peek().is_some() {
+            // Only the first term may leave out its sign.
+            let negative = match self.peek() {
+                Some(c) if c == '+' || c == '-' => {
+                    self.pos += 1;
+                    self.skip_whitespace();
+                    c == '-'
+                }
+                Some(c) if !coeffs.is_empty() => {
+                    return Err(self.error(ParseErrorKind::UnexpectedChar(c)))
+                }
+                _ => false,
+            };
+
+            let (coeff, exp): (T, usize) = self.term()?;
+            if coeffs.len() <= exp {
+                coeffs.resize(exp + 1, T::zero());
+            }
+            if negative {
+                coeffs[exp] -= coeff;
+            } else {
+                coeffs[exp] += coeff;
+            }
+            self.skip_whitespace();
+        }
+
+        Ok(Polynomial::new(coeffs))
+    }
+
+    fn term<T: FromStr + Coefficient>(&mut self) -> Result<(T, usize), ParsePolynomialError> {
+        let coeff = match self.peek() {
+            Some(c) if c.is_ascii_digit() || c == '.' => Some(self.number()?),
+            Some('(') => Some(self.parenthesized()?),
+            _ => None,
+        };
+
+        self.skip_whitespace();
+        let starred = self.peek() == Some('*');
+        if starred {
+            self.pos += 1;
+            self.skip_whitespace();
+        }
+
+        match self.peek() {
+            Some(c) if is_identifier_start(c) => {
+                let exp = self.power()?;
+                Ok((coeff.unwrap_or_else(T::one), exp))
+            }
+            _ if coeff.is_some() && !starred => Ok((coeff.unwrap_or_else(T::one), 0)),
+            Some(c) => Err(self.error(ParseErrorKind::UnexpectedChar(c))),
+            None => Err(self.error(ParseErrorKind::UnexpectedEnd)),
+        }
+    }
+
+    /// Parses a decimal number with an optional exponent and `i` suffix.
+    fn number<T: FromStr>(&mut self) -> Result<T, ParsePolynomialError> {
+        let start = self.pos;
+        self.eat_while(|c| c.is_ascii_digit() || c == '.');
+        if matches!(self.peek(), Some('e') | Some('E')) {
+            let mantissa_end = self.pos;
+            self.pos += 1;
+            if matches!(self.peek(), Some('+') | Some('-')) {
+                self.pos += 1;
+            }
+            if self.eat_while(|c| c.is_ascii_digit()) == 0 {
+                // Not an exponent after all, but the start of a variable.
+                self.pos = mantissa_end;
+            }
+        }
+        if self.peek() == Some('i') && !self.rest()[1..].starts_with(is_identifier_continue) {
+            self.pos += 1;
+        }
+
+        self.input[start..self.pos].parse().map_err(|_| ParsePolynomialError {
+            kind: ParseErrorKind::InvalidCoefficient,
+            position: start,
+        })
+    }
+
+    fn parenthesized<T: FromStr>(&mut self) -> Result<T, ParsePolynomialError> {
+        let start = self.pos;
+        let mut depth = 0;
+        for (i, c) in self.rest().char_indices() {
+            match c {
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                _ => continue,
+            }
+            if depth == 0 {
+                self.pos = start + i + 1;
+                return self.input[start + 1..start + i].trim().parse().map_err(|_| {
+                    ParsePolynomialError {
+                        kind: ParseErrorKind::InvalidCoefficient,
+                        position: start + 1,
+                    }
+                });
+            }
+        }
+
+        Err(ParsePolynomialError { kind: ParseErrorKind::UnclosedParenthesis, position: start })
+    }
+
+    /// Parses the variable and its optional `^exponent` or superscript exponent.
+    fn power(&mut self) -> Result<usize, ParsePolynomialError> {
+        let start = self.pos;
+        self.eat_while(is_identifier_continue);
+        let name = &self.input[start..self.pos];
+        if *self.variable.get_or_insert(name) != name {
+            return Err(ParsePolynomialError {
+                kind: ParseErrorKind::MismatchedVariable,
+                position: start,
+            });
+        }
+
+        let exp_start = self.pos;
+        if self.eat_while(|c| superscript_digit(c).is_some()) > 0 {
+            let exp = self.input[exp_start..self.pos].chars().try_fold(0usize, |acc, c| {
+                acc.checked_mul(10)?.checked_add(superscript_digit(c)? as usize)
+            });
+            return checked_exponent(exp, exp_start);
+        }
+
+        self.skip_whitespace();
+        if self.peek() != Some('^') {
+            return Ok(1);
+        }
+        self.pos += 1;
+        self.skip_whitespace();
+
+        let start = self.pos;
+        match self.peek() {
+            Some(c) if c.is_ascii_digit() => {}
+            Some(_) => return Err(self.error(ParseErrorKind::InvalidExponent)),
+            None => return Err(self.error(ParseErrorKind::UnexpectedEnd)),
+        }
+        self.eat_while(|c| c.is_ascii_digit());
+        checked_exponent(self.input[start..self.pos].parse().ok(), start)
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    /// Advances past the longest prefix matching `pred`, returning its length in bytes.
+    fn eat_while(&mut self, pred: impl Fn(char) -> bool) -> usize {
+        let len = self.rest().find(|c| !pred(c)).unwrap_or(self.rest().len());
+        self.pos += len;
+        len
+    }
+
+    fn skip_whitespace(&mut self) {
+        self.eat_while(char::is_whitespace);
+    }
+
+    fn error(&self, kind: ParseErrorKind) -> ParsePolynomialError {
+        ParsePolynomialError { kind, position: self.pos }
+    }
+}
+
+/// Rejects exponents that did not fit in `usize` or exceed [`MAX_EXPONENT`], reporting them
+/// at `position`.
+fn checked_exponent(exp: Option<usize>, position: usize) -> Result<usize, ParsePolynomialError> {
+    exp.filter(|&exp| exp <= MAX_EXPONENT)
+        .ok_or(ParsePolynomialError { kind: ParseErrorKind::InvalidExponent, position })
+}
+
+fn is_identifier_start(c: char) -> bool {
+    c.is_alphabetic() || c == '_'
+}
+
+fn is_identifier_continue(c: char) -> bool {
+    (c.is_alphanumeric() || c == '_') && superscript_digit(c).is_none()
+}
+
+fn superscript_digit(c: char) -> Option<u32> {
+    SUPERSCRIPTS.iter().position(|s| s.starts_with(c)).map(|d| d as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num::complex::Complex;
+
+    fn parse_error(input: &str) -> (ParseErrorKind, usize) {
+        let err = input.parse::<Polynomial<f64>>().unwrap_err();
+        (err.kind().clone(), err.position())
+    }
+
+    #[test]
+    fn parses_the_documented_notations() {
+        let p: Polynomial<f64> = "3x^2 - 2x + 1".parse().unwrap();
+        assert_eq!(p, Polynomial::new(vec![1., -2., 3.]));
+        let p: Polynomial<f64> = "3*x^2".parse().unwrap();
+        assert_eq!(p, Polynomial::new(vec![0., 0., 3.]));
+        let p: Polynomial<f64> = "-t³ + 2 * t ^ 3 - 0.5".parse().unwrap();
+        assert_eq!(p, Polynomial::new(vec![-0.5, 0., 0., 1.]));
+
+        let p: Polynomial = "(1+2i)x - 3i".parse().unwrap();
+        assert_eq!(p, Polynomial::new(vec![Complex::new(0., -3.), Complex::new(1., 2.)]));
+    }
+
+    #[test]
+    fn reports_error_kinds_and_positions() {
+        assert_eq!(parse_error(""), (ParseErrorKind::Empty, 0));
+        assert_eq!(parse_error("   "), (ParseErrorKind::Empty, 3));
+        assert_eq!(parse_error("3x + $"), (ParseErrorKind::UnexpectedChar('$'), 5));
+        assert_eq!(parse_error("x y"), (ParseErrorKind::UnexpectedChar('y'), 2));
+        assert_eq!(parse_error("3x^2 -"), (ParseErrorKind::UnexpectedEnd, 6));
+        assert_eq!(parse_error("3*"), (ParseErrorKind::UnexpectedEnd, 2));
+        assert_eq!(parse_error("x^"), (ParseErrorKind::UnexpectedEnd, 2));
+        assert_eq!(parse_error("x^2 + y"), (ParseErrorKind::MismatchedVariable, 6));
+        assert_eq!(parse_error("x + (3"), (ParseErrorKind::UnclosedParenthesis, 4));
+        assert_eq!(parse_error("((1)x"), (ParseErrorKind::UnclosedParenthesis, 0));
+        assert_eq!(parse_error("(abc)x"), (ParseErrorKind::InvalidCoefficient, 1));
+        assert_eq!(parse_error("x^-1"), (ParseErrorKind::InvalidExponent, 2));
+    }
+
+    #[test]
+    fn rejects_huge_exponents() {
+        for (input, position) in [
+            ("x^18446744073709551615", 2),
+            ("1 + x^99999999999", 6),
+            ("x¹⁸⁴⁴⁶⁷⁴⁴⁰⁷³⁷⁰⁹⁵⁵¹⁶¹⁶", 1),
+            ("x^16777217", 2),
+        ] {
+            let err = input.parse::<Polynomial<f64>>().unwrap_err();
+            assert_eq!(err.kind(), &ParseErrorKind::InvalidExponent, "{}", input);
+            assert_eq!(err.position(), position, "{}", input);
+        }
+
+        // Parsing `x^MAX_EXPONENT` would allocate every coefficient below it, so check the
+        // limit itself.
+        assert_eq!(checked_exponent(Some(MAX_EXPONENT), 2).unwrap(), MAX_EXPONENT);
+        let err = checked_exponent(Some(MAX_EXPONENT + 1), 2).unwrap_err();
+        assert_eq!((err.kind(), err.position()), (&ParseErrorKind::InvalidExponent, 2));
+        assert!(checked_exponent(None, 0).is_err());
+    }
+}
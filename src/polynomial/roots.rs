@@ -0,0 +1,312 @@
+use super::Polynomial;
+use num::complex::Complex;
+use num::{One, Zero};
+use std::f64::consts::PI;
+
+/// Shifted QR sweeps allowed per eigenvalue of the companion matrix before the current
+/// diagonal entry is accepted as is.
+const MAX_QR_SWEEPS: usize = 60;
+
+/// A root found by [`Polynomial::roots`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Root {
+    /// The approximate root.
+    pub value: Complex<f64>,
+    /// An estimate of the radius of a disc around `value` containing an exact root:
+    /// `n |p(z) / p'(z)|` for a degree `n` polynomial, with the rounding error of evaluating
+    /// `p` added in. Large values flag ill-conditioned roots, such as multiple or clustered
+    /// ones.
+    pub error: f64,
+}
+
+/// Stopping criteria for [`Polynomial::roots_with`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RootOptions {
+    /// An approximation has converged once its correction is at most this much relative to
+    /// its magnitude, or the polynomial vanishes there up to rounding.
+    pub tolerance: f64,
+    /// Sweeps of the Aberth iteration over all approximations before falling back to the
+    /// eigenvalues of the companion matrix.
+    pub max_iterations: usize,
+}
+
+impl Default for RootOptions {
+    fn default() -> Self {
+        RootOptions { tolerance: 1e-12, max_iterations: 100 }
+    }
+}
+
+impl Polynomial<Complex<f64>> {
+    /// Returns every complex root, repeated according to multiplicity, using the default
+    /// [`RootOptions`].
+    pub fn roots(&self) -> Vec<Root> {
+        self.roots_with(&RootOptions::default())
+    }
+
+    /// Returns every complex root, repeated according to multiplicity. Constants, including
+    /// the zero polynomial, have none.
+    ///
+    /// The roots are refined simultaneously with the Aberth-Ehrlich iteration, starting from
+    /// a circle whose radius is the geometric mean of the root magnitudes. If some
+    /// approximation has not converged after `options.max_iterations` sweeps, the roots are
+    /// instead computed as the eigenvalues of the companion matrix with the shifted QR
+    /// algorithm, which is slower but more robust.
+    pub fn roots_with(&self, options: &RootOptions) -> Vec<Root> {
+        let zeros = self.coeffs.iter().take_while(|c| c.is_zero()).count();
+        let mut ret = vec![Root { value: Complex::zero(), error: 0. }; zeros];
+
+        let coeffs = &self.coeffs[zeros..];
+        let lc = match coeffs {
+            [] | [_] => return ret,
+            [.., lc] => *lc,
+        };
+        let monic: Vec<Complex<f64>> = coeffs.iter().map(|c| c / lc).collect();
+
+        let values = aberth(&monic, options).unwrap_or_else(|| companion_eigenvalues(&monic));
+        ret.extend(values.into_iter().map(|z| {
+            let (p, dp, rounding) = evaluate(&monic, z);
+            Root { value: z, error: (monic.len() - 1) as f64 * (p.norm() + rounding) / dp.norm() }
+        }));
+        ret
+    }
+}
+
+/// Runs the Aberth-Ehrlich iteration on a monic polynomial with nonzero constant term,
+/// returning `None` if it does not converge within `options.max_iterations` sweeps.
+fn aberth(coeffs: &[Complex<f64>], options: &RootOptions) -> Option<Vec<Complex<f64>>> {
+    let n = coeffs.len() - 1;
+    let radius = coeffs[0].norm().powf(1. / n as f64);
+    let mut z: Vec<Complex<f64>> =
+        (0..n).map(|k| Complex::from_polar(radius, 2. * PI * k as f64 / n as f64 + 0.4)).collect();
+    let mut converged = vec![false; n];
+
+    for _ in 0..options.max_iterations {
+        for k in 0..n {
+            if converged[k] {
+                continue;
+            }
+
+            let (p, dp, rounding) = evaluate(coeffs, z[k]);
+            if p.norm() <= rounding {
+                converged[k] = true;
+                continue;
+            }
+
+            let ratio = p / dp;
+            let repulsion: Complex<f64> =
+                z.iter().enumerate().filter(|&(j, _)| j != k).map(|(_, w)| (z[k] - w).inv()).sum();
+            let correction = ratio / (1. - ratio * repulsion);
+            if correction.is_finite() {
+                z[k] -= correction;
+                converged[k] = correction.norm() <= options.tolerance * z[k].norm();
+            }
+        }
+
+        if converged.iter().all(|&c| c) {
+            return Some(z);
+        }
+    }
+
+    None
+}
+
+/// Returns `p(z)`, `p'(z)` and a bound on the rounding error of `p(z)`.
+///
+/// Outside the unit circle all three are scaled by `z^-n`, evaluating the reversed
+/// polynomial at `1 / z` instead, so that neither overflows nor loses relative accuracy.
+fn evaluate(coeffs: &[Complex<f64>], z: Complex<f64>) -> (Complex<f64>, Complex<f64>, f64) {
+    let horner = |coeffs: &mut dyn Iterator<Item = &Complex<f64>>, x: Complex<f64>| {
+        let (mut p, mut dp, mut abs) = (Complex::zero(), Complex::zero(), 0.);
+        for c in coeffs {
+            dp = dp * x + p;
+            p = p * x + c;
+            abs = abs * x.norm() + c.norm();
+        }
+        (p, dp, f64::EPSILON * abs)
+    };
+
+    if z.norm() <= 1. {
+        return horner(&mut coeffs.iter().rev(), z);
+    }
+
+    // `p(z) = z^n q(y)` for the reversed polynomial `q` and `y = 1 / z`, so that
+    // `p'(z) = z^n y (n q(y) - y q'(y))`.
+    let y = z.inv();
+    let (q, dq, rounding) = horner(&mut coeffs.iter(), y);
+    let n = (coeffs.len() - 1) as f64;
+    (q, y * (q * n - y * dq), rounding)
+}
+
+/// Returns the eigenvalues of the companion matrix of a monic polynomial, which is already
+/// upper Hessenberg, with the single-shift QR algorithm using Wilkinson shifts and Givens
+/// rotations.
+fn companion_eigenvalues(coeffs: &[Complex<f64>]) -> Vec<Complex<f64>> {
+    let n = coeffs.len() - 1;
+    let mut h = vec![vec![Complex::zero(); n]; n];
+    for (j, c) in coeffs[..n].iter().rev().enumerate() {
+        h[0][j] = -c;
+    }
+    for i in 1..n {
+        h[i][i - 1] = Complex::one();
+    }
+
+    let mut ret = Vec::with_capacity(n);
+    let mut hi = n - 1;
+    let mut sweeps = 0;
+    loop {
+        // Find the start of the unreduced block ending at `hi`.
+        let mut lo = hi;
+        while lo > 0
+            && h[lo][lo - 1].norm() > f64::EPSILON * (h[lo][lo].norm() + h[lo - 1][lo - 1].norm())
+        {
+            lo -= 1;
+        }
+
+        if lo == hi || sweeps == MAX_QR_SWEEPS {
+            ret.push(h[hi][hi]);
+            if hi == 0 {
+                return ret;
+            }
+            hi -= 1;
+            sweeps = 0;
+            continue;
+        }
+        if lo > 0 {
+            h[lo][lo - 1] = Complex::zero();
+        }
+
+        let shift = if sweeps > 0 && sweeps % 10 == 0 {
+            // An exceptional shift breaks the cycles a Wilkinson shift can get stuck in.
+            h[hi][hi] + h[hi][hi - 1].norm()
+        } else {
+            wilkinson_shift(h[hi - 1][hi - 1], h[hi - 1][hi], h[hi][hi - 1], h[hi][hi])
+        };
+        qr_sweep(&mut h, lo, hi, shift);
+        sweeps += 1;
+    }
+}
+
+/// Returns the eigenvalue of `[[a, b], [c, d]]` closer to `d`.
+fn wilkinson_shift(
+    a: Complex<f64>,
+    b: Complex<f64>,
+    c: Complex<f64>,
+    d: Complex<f64>,
+) -> Complex<f64> {
+    let half = (a - d) / 2.;
+    let disc = (half * half + b * c).sqrt();
+    let (x, y) = ((a + d) / 2. + disc, (a + d) / 2. - disc);
+    if (x - d).norm() < (y - d).norm() {
+        x
+    } else {
+        y
+    }
+}
+
+/// Replaces the block `lo..=hi` of `h` by `R Q + shift` where `Q R = h - shift`.
+fn qr_sweep(h: &mut [Vec<Complex<f64>>], lo: usize, hi: usize, shift: Complex<f64>) {
+    for (i, row) in h.iter_mut().enumerate().take(hi + 1).skip(lo) {
+        row[i] -= shift;
+    }
+
+    let mut rotations = Vec::with_capacity(hi - lo);
+    for k in lo..hi {
+        let (x, y) = (h[k][k], h[k + 1][k]);
+        let r = x.norm().hypot(y.norm());
+        let (c, s) = if r == 0. { (Complex::one(), Complex::zero()) } else { (x / r, y / r) };
+        let (upper, lower) = h.split_at_mut(k + 1);
+        for (u, v) in upper[k][k..=hi].iter_mut().zip(lower[0][k..=hi].iter_mut()) {
+            let (x, y) = (*u, *v);
+            *u = c.conj() * x + s.conj() * y;
+            *v = c * y - s * x;
+        }
+        rotations.push((c, s));
+    }
+
+    for (k, (c, s)) in (lo..hi).zip(rotations) {
+        for row in h.iter_mut().take((k + 2).min(hi) + 1).skip(lo) {
+            let (u, v) = (row[k], row[k + 1]);
+            row[k] = u * c + v * s;
+            row[k + 1] = v * c.conj() - u * s.conj();
+        }
+    }
+
+    for (i, row) in h.iter_mut().enumerate().take(hi + 1).skip(lo) {
+        row[i] += shift;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn from_roots(roots: &[Complex<f64>]) -> Polynomial {
+        let mut coeffs = vec![Complex::one()];
+        for &r in roots {
+            coeffs.insert(0, Complex::zero());
+            for i in 0..coeffs.len() - 1 {
+                let carry = coeffs[i + 1] * r;
+                coeffs[i] -= carry;
+            }
+        }
+        Polynomial::new(coeffs)
+    }
+
+    /// Checks that every expected root is matched by a distinct found root within `tolerance`.
+    fn assert_roots(found: &[Root], expected: &[Complex<f64>], tolerance: f64) {
+        assert_eq!(found.len(), expected.len());
+        let mut unused: Vec<Complex<f64>> = found.iter().map(|r| r.value).collect();
+        for e in expected {
+            let (i, distance) = unused
+                .iter()
+                .map(|z| (z - e).norm())
+                .enumerate()
+                .fold((0, f64::INFINITY), |best, (i, d)| if d < best.1 { (i, d) } else { best });
+            assert!(distance < tolerance, "no root near {} (closest {})", e, distance);
+            unused.swap_remove(i);
+        }
+    }
+
+    fn sample_roots() -> Vec<Complex<f64>> {
+        vec![
+            Complex::new(1., 0.),
+            Complex::new(-2., 0.),
+            Complex::new(0.5, 1.5),
+            Complex::new(0.5, -1.5),
+            Complex::new(-3., 4.),
+            Complex::new(0., -0.25),
+            Complex::new(7., 0.),
+        ]
+    }
+
+    #[test]
+    fn aberth_finds_known_roots() {
+        let roots = sample_roots();
+        let found = from_roots(&roots).roots();
+        assert_roots(&found, &roots, 1e-9);
+        assert!(found.iter().all(|r| r.error < 1e-6));
+    }
+
+    #[test]
+    fn companion_fallback_finds_known_roots() {
+        let roots = sample_roots();
+        let p = from_roots(&roots);
+        let options = RootOptions { tolerance: 1e-12, max_iterations: 0 };
+        assert_roots(&p.roots_with(&options), &roots, 1e-8);
+
+        let wilkinson: Vec<Complex<f64>> = (1..=12).map(|k| Complex::from(k as f64)).collect();
+        let eigenvalues = companion_eigenvalues(&from_roots(&wilkinson).coeffs);
+        let found: Vec<Root> =
+            eigenvalues.into_iter().map(|value| Root { value, error: 0. }).collect();
+        assert_roots(&found, &wilkinson, 1e-4);
+    }
+
+    #[test]
+    fn zero_roots_are_split_off() {
+        let roots = [Complex::zero(), Complex::zero(), Complex::new(2., 0.)];
+        let found = from_roots(&roots).roots();
+        assert_roots(&found, &roots, 1e-12);
+        assert!(Polynomial::new(vec![Complex::new(3., 0.)]).roots().is_empty());
+        assert!(Polynomial::zero().roots().is_empty());
+    }
+}
@@ -0,0 +1,147 @@
+use super::{Polynomial, MAX_EXPONENT};
+use crate::coefficient::Coefficient;
+use serde::de::{Deserialize, Deserializer, Error, MapAccess, Unexpected, Visitor};
+use serde::ser::{Serialize, Serializer};
+use std::fmt;
+use std::marker::PhantomData;
+
+/// Serializes as the array of coefficients in ascending order of exponent.
+impl<T: Coefficient + Serialize> Serialize for Polynomial<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(&self.coeffs)
+    }
+}
+
+/// Deserializes from the array of coefficients in ascending order of exponent. Trailing
+/// zeros are stripped as in [`Polynomial::new`].
+impl<'de, T: Coefficient + Deserialize<'de>> Deserialize<'de> for Polynomial<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::deserialize(deserializer).map(Polynomial::new)
+    }
+}
+
+/// Serde helpers storing a [`Polynomial`] as a map from exponent to nonzero coefficient,
+/// which stays small for polynomials such as `x^1000000 + 1`.
+///
+/// Use it on a field with `#[serde(with = "poly_fft::serde_sparse")]`.
+pub mod serde_sparse {
+    use super::*;
+
+    /// Serializes the nonzero terms of `poly` as an `{exponent: coefficient}` map.
+    pub fn serialize<T, S>(poly: &Polynomial<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Coefficient + Serialize,
+        S: Serializer,
+    {
+        serializer.collect_map(poly.coeffs.iter().enumerate().filter(|(_, c)| !c.is_zero()))
+    }
+
+    /// Deserializes an `{exponent: coefficient}` map, adding up repeated exponents.
+    /// Exponents above [`MAX_EXPONENT`](crate::MAX_EXPONENT) are rejected.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Polynomial<T>, D::Error>
+    where
+        T: Coefficient + Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(SparseVisitor(PhantomData))
+    }
+}
+
+struct SparseVisitor<T>(PhantomData<T>);
+
+impl<'de, T: Coefficient + Deserialize<'de>> Visitor<'de> for SparseVisitor<T> {
+    type Value = Polynomial<T>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a map from exponents to coefficients")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut coeffs = Vec::new();
+        while let Some((exp, coeff)) = map.next_entry::<usize, T>()? {
+            if exp > MAX_EXPONENT {
+                let expected = format!("an exponent of at most {}", MAX_EXPONENT);
+                return Err(A::Error::invalid_value(
+                    Unexpected::Unsigned(exp as u64),
+                    &expected.as_str(),
+                ));
+            }
+            if coeffs.len() <= exp {
+                coeffs.resize(exp + 1, T::zero());
+            }
+            coeffs[exp] += coeff;
+        }
+
+        Ok(Polynomial::new(coeffs))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ntt::ModInt;
+    use num::complex::Complex;
+    use serde::de::value::{Error as ValueError, MapDeserializer};
+
+    fn from_map(entries: Vec<(usize, f64)>) -> Result<Polynomial<f64>, ValueError> {
+        serde_sparse::deserialize(MapDeserializer::new(entries.into_iter()))
+    }
+
+    #[test]
+    fn sparse_map_adds_repeated_exponents() {
+        let poly = from_map(vec![(3, 1.), (0, 2.), (3, 0.5)]).unwrap();
+        assert_eq!(poly, Polynomial::new(vec![2., 0., 0., 1.5]));
+    }
+
+    #[test]
+    fn sparse_map_rejects_huge_exponents() {
+        assert!(from_map(vec![(usize::MAX, 1.)]).is_err());
+        assert!(from_map(vec![(MAX_EXPONENT + 1, 1.)]).is_err());
+        assert_eq!(from_map(vec![(MAX_EXPONENT, 1.)]).unwrap().degree(), Some(MAX_EXPONENT));
+    }
+
+    #[test]
+    fn dense_round_trips_complex_coefficients() {
+        let p: Polynomial = Polynomial::new(vec![
+            Complex::new(1., -2.),
+            Complex::new(0., 0.),
+            Complex::new(0.5, 3.),
+        ]);
+        let json = serde_json::to_string(&p).unwrap();
+        assert_eq!(json, "[[1.0,-2.0],[0.0,0.0],[0.5,3.0]]");
+        assert_eq!(serde_json::from_str::<Polynomial>(&json).unwrap(), p);
+
+        let trailing = serde_json::from_str::<Polynomial>("[[1.0,0.0],[0.0,0.0]]").unwrap();
+        assert_eq!(trailing, Polynomial::new(vec![Complex::new(1., 0.)]));
+        assert_eq!(serde_json::to_string(&Polynomial::<Complex<f64>>::zero()).unwrap(), "[]");
+    }
+
+    #[test]
+    fn dense_round_trips_mod_int_coefficients() {
+        let p: Polynomial<ModInt<97>> = Polynomial::from(vec![3, -1, 0, 7]);
+        let json = serde_json::to_string(&p).unwrap();
+        assert_eq!(json, "[3,96,0,7]");
+        assert_eq!(serde_json::from_str::<Polynomial<ModInt<97>>>(&json).unwrap(), p);
+        assert!(serde_json::from_str::<Polynomial<ModInt<97>>>("[3,97]").is_err());
+    }
+
+    #[test]
+    fn sparse_round_trips_nonzero_terms() {
+        let p = Polynomial::new(vec![2., 0., 0., 0., 0., -1.5]);
+        let mut json = Vec::new();
+        serde_sparse::serialize(&p, &mut serde_json::Serializer::new(&mut json)).unwrap();
+        assert_eq!(String::from_utf8(json.clone()).unwrap(), r#"{"0":2.0,"5":-1.5}"#);
+
+        let back: Polynomial<f64> =
+            serde_sparse::deserialize(&mut serde_json::Deserializer::from_slice(&json)).unwrap();
+        assert_eq!(back, p);
+
+        let mut empty = Vec::new();
+        serde_sparse::serialize(
+            &Polynomial::<f64>::zero(),
+            &mut serde_json::Serializer::new(&mut empty),
+        )
+        .unwrap();
+        assert_eq!(empty, b"{}");
+    }
+}
@@ -0,0 +1,289 @@
+use super::Polynomial;
+use crate::coefficient::{Coefficient, Field, SquareRoot};
+
+/// Truncated power series arithmetic: every method returns the first `n` coefficients of the
+/// series, computed by Newton iteration on top of the multiplication backend of `T`.
+impl<T: Field> Polynomial<T> {
+    /// Returns `g` with `self * g = 1 mod x^n`, doubling the number of correct coefficients
+    /// with each step `g <- g - g (self g - 1)`.
+    ///
+    /// Panics if the constant term is zero.
+    pub fn inv_series(&self, n: usize) -> Self {
+        assert!(!self.coeff(0).is_zero(), "series with zero constant term has no inverse");
+        if n == 0 {
+            return Polynomial::zero();
+        }
+
+        let mut g = vec![T::one() / self.coeff(0)];
+        while g.len() < n {
+            let len = (2 * g.len()).min(n);
+
+            let mut e = mul_trunc(self.truncated(len), &g, len);
+            e[0] -= T::one();
+
+            let ge = mul_trunc(&g, &e, len);
+            g.resize(len, T::zero());
+            g.iter_mut().zip(ge).for_each(|(x, y)| *x -= y);
+        }
+
+        Polynomial::new(g)
+    }
+
+    /// Returns `log(self) mod x^n` as the integral of `self' / self`.
+    ///
+    /// The integration constant is zero, so for a constant term `c` other than one this is
+    /// the logarithm of `self / c`. Panics if the constant term is zero.
+    pub fn log_series(&self, n: usize) -> Self {
+        if n == 0 {
+            return Polynomial::zero();
+        }
+
+        let derivative = Polynomial::new(self.truncated(n).to_vec()).derivative();
+        let quotient = mul_trunc(&derivative.coeffs, &self.inv_series(n).coeffs, n - 1);
+        Polynomial::new(quotient).integral(T::zero())
+    }
+
+    /// Returns `exp(self) mod x^n` by the Newton step `g <- g (1 - log(g) + self)`.
+    ///
+    /// Panics unless the constant term is zero.
+    pub fn exp_series(&self, n: usize) -> Self {
+        assert!(self.coeff(0).is_zero(), "series exponential needs constant term zero");
+        if n == 0 {
+            return Polynomial::zero();
+        }
+
+        let mut g = Polynomial::new(vec![T::one()]);
+        let mut len = 1;
+        while len < n {
+            len = (2 * len).min(n);
+
+            let mut e = self.truncated(len).to_vec();
+            e.resize(len, T::zero());
+            e.iter_mut().zip(g.log_series(len).coeffs).for_each(|(x, y)| *x -= y);
+            e[0] += T::one();
+
+            g = Polynomial::new(mul_trunc(&g.coeffs, &e, len));
+        }
+
+        g
+    }
+
+    /// Returns `self^k mod x^n` as `c^k x^(t k) exp(k log(h))`, where `self = c x^t h` and `h`
+    /// has constant term one.
+    pub fn pow_series(&self, k: u64, n: usize) -> Self {
+        if k == 0 {
+            return Polynomial::new(vec![T::one()]).truncate_series(n);
+        }
+        let t = match self.coeffs.iter().position(|c| !c.is_zero()) {
+            Some(t) => t,
+            None => return Polynomial::zero(),
+        };
+        let shift = match (t as u64).checked_mul(k) {
+            Some(shift) if shift < n as u64 => shift as usize,
+            _ => return Polynomial::zero(),
+        };
+
+        let c = self.coeffs[t].clone();
+        let c_inv = T::one() / c.clone();
+        let mut h: Vec<T> = self.coeffs[t..].iter().map(|x| x.clone() * c_inv.clone()).collect();
+        h[0] = T::one();
+        let h = Polynomial::new(h);
+
+        let m = n - shift;
+        let powered = (h.log_series(m) * from_u64::<T>(k)).exp_series(m) * pow(c, k);
+
+        let mut coeffs = vec![T::zero(); shift];
+        coeffs.extend(powered.coeffs);
+        Polynomial::new(coeffs)
+    }
+
+    /// Drops every coefficient of degree `n` or more.
+    pub fn truncate_series(mut self, n: usize) -> Self {
+        self.coeffs.truncate(n);
+        self.trim();
+        self
+    }
+
+    /// Returns the coefficients of `self.truncate_series(n)` without copying.
+    fn truncated(&self, n: usize) -> &[T] {
+        &self.coeffs[..self.coeffs.len().min(n)]
+    }
+}
+
+impl<T: Field + SquareRoot> Polynomial<T> {
+    /// Returns a square root `g` of `self` modulo `x^n` by the Newton step
+    /// `g <- (g + self / g) / 2`, or `None` if there is none: the lowest nonzero term must
+    /// have even degree and a coefficient with a square root in `T`.
+    pub fn sqrt_series(&self, n: usize) -> Option<Self> {
+        let t = match self.coeffs.iter().position(|c| !c.is_zero()) {
+            Some(t) => t,
+            None => return Some(Polynomial::zero()),
+        };
+        if t % 2 == 1 {
+            return None;
+        }
+        if t / 2 >= n {
+            return Some(Polynomial::zero());
+        }
+
+        let shifted = Polynomial { coeffs: self.coeffs[t..].to_vec() };
+        let m = n - t / 2;
+        let inv_two = T::one() / (T::one() + T::one());
+
+        let mut g = vec![self.coeffs[t].square_root()?];
+        while g.len() < m {
+            let len = (2 * g.len()).min(m);
+
+            let g_inv = Polynomial { coeffs: g.clone() }.inv_series(len);
+            let quotient = mul_trunc(shifted.truncated(len), &g_inv.coeffs, len);
+
+            g.resize(len, T::zero());
+            g.iter_mut().zip(quotient).for_each(|(x, y)| *x = (x.clone() + y) * inv_two.clone());
+        }
+
+        let mut coeffs = vec![T::zero(); t / 2];
+        coeffs.extend(g);
+        Some(Polynomial::new(coeffs))
+    }
+}
+
+/// Returns the product of `a` and `b` truncated or zero-padded to exactly `n` coefficients.
+fn mul_trunc<T: Field>(a: &[T], b: &[T], n: usize) -> Vec<T> {
+    let mut ret = T::convolve(a, b);
+    ret.resize(n, T::zero());
+    ret
+}
+
+/// Returns `k` as an element of `T` by double-and-add on `T::one()`.
+pub(super) fn from_u64<T: Coefficient>(k: u64) -> T {
+    (0..u64::BITS - k.leading_zeros()).rev().fold(T::zero(), |acc, bit| {
+        let acc = acc.clone() + acc;
+        if k >> bit & 1 == 1 {
+            acc + T::one()
+        } else {
+            acc
+        }
+    })
+}
+
+pub(super) fn pow<T: Coefficient>(mut base: T, mut exp: u64) -> T {
+    let mut ret = T::one();
+    while exp > 0 {
+        if exp & 1 == 1 {
+            ret *= base.clone();
+        }
+        base = base.clone() * base;
+        exp >>= 1;
+    }
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ntt::ModInt;
+    use crate::test_util::{random_poly, M};
+    use num::{One, Zero};
+
+    fn pow_naive<const P: u32>(
+        base: &Polynomial<ModInt<P>>,
+        mut k: u64,
+        n: usize,
+    ) -> Polynomial<ModInt<P>> {
+        let mut base = base.clone().truncate_series(n);
+        let mut ret = Polynomial::new(vec![ModInt::one()]).truncate_series(n);
+        while k > 0 {
+            if k & 1 == 1 {
+                ret = (&ret * &base).truncate_series(n);
+            }
+            base = (&base * &base).truncate_series(n);
+            k >>= 1;
+        }
+        ret
+    }
+
+    fn with_constant(mut p: Polynomial<M>, c: u64) -> Polynomial<M> {
+        p.coeffs[0] = M::new(c);
+        p
+    }
+
+    #[test]
+    fn inv_series_inverts() {
+        let a = with_constant(random_poly(120, 1), 7);
+        for &n in &[0, 1, 2, 77, 128, 200] {
+            let g = a.inv_series(n);
+            let expected = Polynomial::new(vec![M::one()]).truncate_series(n);
+            assert_eq!((&a * &g).truncate_series(n), expected, "n = {}", n);
+            assert!(g.coeffs.len() <= n);
+        }
+    }
+
+    #[test]
+    fn log_and_exp_series_round_trip() {
+        let n = 150;
+        let f = with_constant(random_poly(100, 2), 0);
+        assert_eq!(f.exp_series(n).log_series(n), f);
+
+        let g = with_constant(random_poly(n, 3), 1);
+        assert_eq!(g.log_series(n).exp_series(n), g);
+    }
+
+    #[test]
+    fn exp_series_of_x_is_the_factorial_series() {
+        let x = Polynomial::new(vec![0., 1.]);
+        let mut factorial = 1.;
+        for (i, c) in x.exp_series(15).iter().enumerate() {
+            factorial *= i.max(1) as f64;
+            assert!((c - 1. / factorial).abs() < 1e-15, "coefficient {} is {}", i, c);
+        }
+    }
+
+    #[test]
+    fn sqrt_series_squares_back() {
+        let n = 100;
+        let g = with_constant(random_poly(80, 4), 5);
+        let square = (&g * &g).truncate_series(n);
+
+        let root = square.sqrt_series(n).unwrap();
+        assert_eq!((&root * &root).truncate_series(n), square);
+
+        // A square root of `x^4 s` is `x^2` times one of `s`.
+        let shifted = Polynomial::new([vec![M::zero(); 4], square.coeffs.clone()].concat());
+        let root = shifted.sqrt_series(n).unwrap();
+        assert!(root.coeffs[..2].iter().all(Zero::is_zero));
+        assert_eq!((&root * &root).truncate_series(n), shifted.clone().truncate_series(n));
+
+        let odd = Polynomial::new([vec![M::zero(); 3], square.coeffs.clone()].concat());
+        assert_eq!(odd.sqrt_series(n), None);
+
+        // 3 is a quadratic non-residue modulo 998244353.
+        assert_eq!(with_constant(square, 3).sqrt_series(n), None);
+    }
+
+    #[test]
+    fn pow_series_matches_repeated_multiplication() {
+        let n = 60;
+        // Lowest term `x^2`, so the result is shifted by `2 k`.
+        let a = Polynomial::new([vec![M::zero(); 2], random_poly::<M>(40, 5).coeffs].concat());
+        for &k in &[0, 1, 2, 7, 29, 30] {
+            assert_eq!(a.pow_series(k, n), pow_naive(&a, k, n), "k = {}", k);
+        }
+        assert!(Polynomial::<M>::zero().pow_series(3, n).is_zero());
+    }
+
+    #[test]
+    fn pow_series_with_exponent_past_the_modulus() {
+        // `k` is only known modulo `P` in `exp(k log(h))`, which is still exact because
+        // `h^P = h(x^P) = 1 mod x^n` for `n <= P`.
+        let n = 20;
+        let a = random_poly::<ModInt<97>>(15, 6);
+        assert!(!a.coeff(0).is_zero());
+        for &k in &[97, 100, 194, 1000] {
+            assert_eq!(a.pow_series(k, n), pow_naive(&a, k, n), "k = {}", k);
+        }
+
+        let b = with_constant(random_poly(50, 7), 9);
+        let k = M::MODULUS as u64 + 3;
+        assert_eq!(b.pow_series(k, n), pow_naive(&b, k, n));
+    }
+}
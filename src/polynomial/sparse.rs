@@ -0,0 +1,351 @@
+use super::series::pow;
+use super::Polynomial;
+use crate::coefficient::Coefficient;
+use num::complex::Complex;
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// Dense multiplication is assumed to cost this many times `n log2 n` for a product of
+/// length `n`, against `log2 k` per pair of terms for the heap merge over `k` rows.
+const DENSE_COST_FACTOR: usize = 8;
+
+/// A polynomial stored as its nonzero terms, for polynomials such as `x^1000000 + 1` whose
+/// dense coefficients would be mostly zero.
+///
+/// The terms are kept sorted by exponent, with no repeated exponents and no zero
+/// coefficients, so equal polynomials have equal representations.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct SparsePolynomial<T = Complex<f64>> {
+    terms: Vec<(usize, T)>,
+}
+
+impl<T: Coefficient> SparsePolynomial<T> {
+    /// Creates a polynomial from `(exponent, coefficient)` pairs in any order, adding up
+    /// repeated exponents.
+    pub fn new(mut terms: Vec<(usize, T)>) -> Self {
+        terms.sort_by_key(|&(exp, _)| exp);
+
+        let mut ret: Vec<(usize, T)> = Vec::with_capacity(terms.len());
+        for (exp, c) in terms {
+            match ret.last_mut() {
+                Some((last, acc)) if *last == exp => *acc += c,
+                _ => {
+                    if ret.last().is_some_and(|(_, acc)| acc.is_zero()) {
+                        ret.pop();
+                    }
+                    ret.push((exp, c));
+                }
+            }
+        }
+        if ret.last().is_some_and(|(_, acc)| acc.is_zero()) {
+            ret.pop();
+        }
+
+        SparsePolynomial { terms: ret }
+    }
+
+    pub fn zero() -> Self {
+        SparsePolynomial { terms: Vec::new() }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// Returns the number of nonzero terms.
+    pub fn len(&self) -> usize {
+        self.terms.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// Returns the degree, or `None` for the zero polynomial.
+    pub fn degree(&self) -> Option<usize> {
+        self.terms.last().map(|&(exp, _)| exp)
+    }
+
+    /// Returns the coefficient of `x^exp`, which is zero for absent terms.
+    pub fn coeff(&self, exp: usize) -> T {
+        match self.terms.binary_search_by_key(&exp, |&(e, _)| e) {
+            Ok(i) => self.terms[i].1.clone(),
+            Err(_) => T::zero(),
+        }
+    }
+
+    /// Returns the nonzero terms as `(exponent, coefficient)` pairs in ascending order of
+    /// exponent.
+    pub fn terms(&self) -> &[(usize, T)] {
+        &self.terms
+    }
+
+    pub fn leading_coefficient(&self) -> Option<&T> {
+        self.terms.last().map(|(_, c)| c)
+    }
+
+    pub fn into_terms(self) -> Vec<(usize, T)> {
+        self.terms
+    }
+
+    /// Evaluates the polynomial at `x`, raising `x` from one exponent to the next by
+    /// repeated squaring.
+    pub fn eval(&self, x: &T) -> T {
+        let mut ret = T::zero();
+        let (mut power, mut prev) = (T::one(), 0);
+        for (exp, c) in &self.terms {
+            power *= pow(x.clone(), (exp - prev) as u64);
+            prev = *exp;
+            ret += c.clone() * power.clone();
+        }
+        ret
+    }
+
+    /// Multiplies by merging the rows `a_i x^(e_i) * rhs` with a binary heap, which takes
+    /// `O(s t log s)` for `s <= t` terms and never looks at the absent exponents.
+    fn mul_sparse(&self, rhs: &Self) -> Self {
+        let (a, b) = if self.len() <= rhs.len() { (self, rhs) } else { (rhs, self) };
+        if a.is_zero() {
+            return SparsePolynomial::zero();
+        }
+
+        let exponent = |i: usize, j: usize| {
+            a.terms[i].0.checked_add(b.terms[j].0).expect("exponent overflows usize")
+        };
+        let mut heap: BinaryHeap<Reverse<(usize, usize, usize)>> =
+            (0..a.len()).map(|i| Reverse((exponent(i, 0), i, 0))).collect();
+
+        let mut terms: Vec<(usize, T)> = Vec::new();
+        while let Some(Reverse((exp, i, j))) = heap.pop() {
+            let product = a.terms[i].1.clone() * b.terms[j].1.clone();
+            match terms.last_mut() {
+                Some((last, acc)) if *last == exp => *acc += product,
+                _ => {
+                    if terms.last().is_some_and(|(_, acc)| acc.is_zero()) {
+                        terms.pop();
+                    }
+                    terms.push((exp, product));
+                }
+            }
+            if j + 1 < b.len() {
+                heap.push(Reverse((exponent(i, j + 1), i, j + 1)));
+            }
+        }
+        if terms.last().is_some_and(|(_, acc)| acc.is_zero()) {
+            terms.pop();
+        }
+
+        SparsePolynomial { terms }
+    }
+
+    /// Whether the heap merge is expected to beat a dense product of the same operands.
+    fn prefers_sparse(&self, rhs: &Self) -> bool {
+        let (s, t) = (self.len().min(rhs.len()), self.len().max(rhs.len()));
+        let n = match (self.degree(), rhs.degree()) {
+            (Some(d), Some(e)) => d.saturating_add(e).saturating_add(1),
+            _ => return true,
+        };
+
+        let sparse = s.saturating_mul(t).saturating_mul(log2(s));
+        let dense = n.saturating_mul(log2(n)).saturating_mul(DENSE_COST_FACTOR);
+        sparse <= dense
+    }
+
+    /// Adds `sign(c)` for every term of `rhs` to `self`, merging the sorted term lists.
+    fn merge(&self, rhs: &Self, sign: impl Fn(T) -> T) -> Self {
+        let mut terms = Vec::with_capacity(self.len() + rhs.len());
+        let (mut a, mut b) = (self.terms.iter().peekable(), rhs.terms.iter().peekable());
+        loop {
+            let order = match (a.peek(), b.peek()) {
+                (Some((e, _)), Some((f, _))) => e.cmp(f),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => break,
+            };
+            match order {
+                Ordering::Less => terms.extend(a.next().cloned()),
+                Ordering::Greater => terms.extend(b.next().map(|(e, c)| (*e, sign(c.clone())))),
+                Ordering::Equal => {
+                    if let (Some((e, x)), Some((_, y))) = (a.next(), b.next()) {
+                        let sum = x.clone() + sign(y.clone());
+                        if !sum.is_zero() {
+                            terms.push((*e, sum));
+                        }
+                    }
+                }
+            }
+        }
+
+        SparsePolynomial { terms }
+    }
+}
+
+impl<T: Coefficient> From<Polynomial<T>> for SparsePolynomial<T> {
+    fn from(poly: Polynomial<T>) -> Self {
+        let terms = poly.coeffs.into_iter().enumerate().filter(|(_, c)| !c.is_zero()).collect();
+        SparsePolynomial { terms }
+    }
+}
+
+impl<T: Coefficient> From<SparsePolynomial<T>> for Polynomial<T> {
+    fn from(poly: SparsePolynomial<T>) -> Self {
+        let mut coeffs = vec![T::zero(); poly.degree().map_or(0, |d| d + 1)];
+        for (exp, c) in poly.terms {
+            coeffs[exp] = c;
+        }
+        Polynomial { coeffs }
+    }
+}
+
+impl<T: Coefficient> Add<&SparsePolynomial<T>> for &SparsePolynomial<T> {
+    type Output = SparsePolynomial<T>;
+
+    fn add(self, rhs: &SparsePolynomial<T>) -> SparsePolynomial<T> {
+        self.merge(rhs, |c| c)
+    }
+}
+
+impl<T: Coefficient> Sub<&SparsePolynomial<T>> for &SparsePolynomial<T> {
+    type Output = SparsePolynomial<T>;
+
+    fn sub(self, rhs: &SparsePolynomial<T>) -> SparsePolynomial<T> {
+        self.merge(rhs, |c| -c)
+    }
+}
+
+impl<T: Coefficient> Mul<&SparsePolynomial<T>> for &SparsePolynomial<T> {
+    type Output = SparsePolynomial<T>;
+
+    /// Uses the heap merge when few terms are present relative to the degree of the
+    /// product, and the dense multiplication of `T` otherwise.
+    fn mul(self, rhs: &SparsePolynomial<T>) -> SparsePolynomial<T> {
+        if self.prefers_sparse(rhs) {
+            return self.mul_sparse(rhs);
+        }
+
+        let dense = Polynomial::from(self.clone()) * Polynomial::from(rhs.clone());
+        SparsePolynomial::from(dense)
+    }
+}
+
+impl<T: Coefficient> Neg for SparsePolynomial<T> {
+    type Output = SparsePolynomial<T>;
+
+    fn neg(mut self) -> SparsePolynomial<T> {
+        self.terms.iter_mut().for_each(|(_, c)| *c = -c.clone());
+        self
+    }
+}
+
+impl<T: Coefficient> Neg for &SparsePolynomial<T> {
+    type Output = SparsePolynomial<T>;
+
+    fn neg(self) -> SparsePolynomial<T> {
+        -self.clone()
+    }
+}
+
+impl<T: Coefficient> Mul<T> for SparsePolynomial<T> {
+    type Output = SparsePolynomial<T>;
+
+    fn mul(mut self, rhs: T) -> SparsePolynomial<T> {
+        self.terms.iter_mut().for_each(|(_, c)| *c *= rhs.clone());
+        self.terms.retain(|(_, c)| !c.is_zero());
+        self
+    }
+}
+
+impl<T: Coefficient> Mul<T> for &SparsePolynomial<T> {
+    type Output = SparsePolynomial<T>;
+
+    fn mul(self, rhs: T) -> SparsePolynomial<T> {
+        self.clone() * rhs
+    }
+}
+
+forward_owned_binop! {
+    SparsePolynomial;
+    impl Add, add, AddAssign, add_assign;
+    impl Sub, sub, SubAssign, sub_assign;
+    impl Mul, mul, MulAssign, mul_assign;
+}
+
+/// Returns `ceil(log2(n))`, at least one.
+fn log2(n: usize) -> usize {
+    (n.next_power_of_two().trailing_zeros() as usize).max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::random_vec;
+
+    type Sparse = SparsePolynomial<i64>;
+
+    fn random(len: usize, max_exp: usize, seed: u64) -> Sparse {
+        let exps = random_vec::<u64>(len, seed).into_iter().map(|e| (e >> 33) as usize % max_exp);
+        SparsePolynomial::new(exps.zip(random_vec(len, seed + 1)).collect())
+    }
+
+    fn dense_product(a: &Sparse, b: &Sparse) -> Sparse {
+        SparsePolynomial::from(Polynomial::from(a.clone()) * Polynomial::from(b.clone()))
+    }
+
+    #[test]
+    fn mul_sparse_matches_dense_product() {
+        for &(s, t, max_exp) in &[(1, 1, 10), (5, 40, 100), (30, 30, 2000), (60, 7, 50)] {
+            let a = random(s, max_exp, s as u64);
+            let b = random(t, max_exp, t as u64 + 100);
+            assert_eq!(a.mul_sparse(&b), dense_product(&a, &b), "{} x {}", s, t);
+            assert_eq!(&a * &b, dense_product(&a, &b));
+        }
+        assert!(random(5, 10, 1).mul_sparse(&SparsePolynomial::zero()).is_zero());
+    }
+
+    #[test]
+    fn new_adds_repeated_exponents_and_drops_zeros() {
+        let p = SparsePolynomial::new(vec![(3, 2), (1, 5), (3, -2), (0, 0), (7, 4), (1, -5)]);
+        assert_eq!(p.terms(), &[(7, 4)]);
+        assert!(SparsePolynomial::new(vec![(2, 1), (2, -1)]).is_zero());
+        assert_eq!(SparsePolynomial::new(vec![(4, 1), (4, 2), (0, 0)]).terms(), &[(4, 3)]);
+    }
+
+    #[test]
+    fn mul_sparse_cancels_terms() {
+        // `(x + 1) (x^2 - x + 1) = x^3 + 1`.
+        let a = SparsePolynomial::new(vec![(1, 1), (0, 1)]);
+        let b = SparsePolynomial::new(vec![(2, 1), (1, -1), (0, 1)]);
+        assert_eq!(a.mul_sparse(&b).terms(), &[(0, 1), (3, 1)]);
+
+        // `(x^1000000 + 1) (x^1000000 - 1) = x^2000000 - 1`.
+        let a = SparsePolynomial::new(vec![(1_000_000, 1), (0, 1)]);
+        let b = SparsePolynomial::new(vec![(1_000_000, 1), (0, -1)]);
+        assert_eq!((&a * &b).terms(), &[(0, -1), (2_000_000, 1)]);
+
+        // Cancellation in the last term.
+        let c = SparsePolynomial::new(vec![(1, 1), (0, -1)]);
+        let d = SparsePolynomial::new(vec![(1, 1), (0, 1)]);
+        let e = SparsePolynomial::new(vec![(2, 1), (1, 1), (0, 1)]);
+        assert_eq!((&c * &d).mul_sparse(&e).terms(), &[(0, -1), (1, -1), (3, 1), (4, 1)]);
+    }
+
+    #[test]
+    fn merge_cancels_terms() {
+        let a = SparsePolynomial::new(vec![(5, 1), (1, 1)]);
+        let b = SparsePolynomial::new(vec![(5, -1), (0, 2)]);
+        assert_eq!((&a + &b).terms(), &[(0, 2), (1, 1)]);
+        assert!((&a - &a).is_zero());
+        assert_eq!((&a - &b).terms(), &[(0, -2), (1, 1), (5, 2)]);
+    }
+
+    #[test]
+    fn prefers_sparse_for_few_terms_and_dense_otherwise() {
+        let sparse = SparsePolynomial::new(vec![(1_000_000, 1), (0, 1)]);
+        assert!(sparse.prefers_sparse(&sparse));
+
+        let dense = SparsePolynomial::from(Polynomial::new((1..=100).collect()));
+        assert_eq!(dense.len(), 100);
+        assert!(!dense.prefers_sparse(&dense));
+    }
+}
@@ -0,0 +1,83 @@
+//! Helpers shared by the unit tests.
+
+use crate::coefficient::{Coefficient, Magnitude};
+use crate::ntt::{Mod998244353, ModInt};
+use crate::polynomial::Polynomial;
+use num::complex::Complex;
+
+/// The NTT-friendly modulus the exact tests compute over.
+pub(crate) type M = Mod998244353;
+
+/// A 64-bit linear congruential generator, which makes the random test inputs reproducible
+/// without pulling in a dependency.
+pub(crate) struct Lcg(u64);
+
+impl Lcg {
+    pub(crate) fn new(seed: u64) -> Self {
+        Lcg(seed)
+    }
+
+    /// Returns the next state. Its high bits are the most random ones.
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        self.0
+    }
+}
+
+/// Values the tests draw at random.
+pub(crate) trait Random {
+    fn random(rng: &mut Lcg) -> Self;
+}
+
+/// Every bit is random.
+impl Random for u64 {
+    fn random(rng: &mut Lcg) -> Self {
+        rng.next_u64()
+    }
+}
+
+/// Uniform in `[-512, 512)`, small enough that products of long inputs stay far from overflow.
+impl Random for i64 {
+    fn random(rng: &mut Lcg) -> Self {
+        (rng.next_u64() >> 54) as i64 - 512
+    }
+}
+
+/// Uniform in `[-0.5, 0.5)`.
+impl Random for f64 {
+    fn random(rng: &mut Lcg) -> Self {
+        (rng.next_u64() >> 11) as f64 / (1u64 << 53) as f64 - 0.5
+    }
+}
+
+impl Random for Complex<f64> {
+    fn random(rng: &mut Lcg) -> Self {
+        Complex::new(f64::random(rng), f64::random(rng))
+    }
+}
+
+impl<const P: u32> Random for ModInt<P> {
+    fn random(rng: &mut Lcg) -> Self {
+        ModInt::new(rng.next_u64() >> 33)
+    }
+}
+
+/// Returns `len` random values from the generator seeded with `seed`.
+pub(crate) fn random_vec<T: Random>(len: usize, seed: u64) -> Vec<T> {
+    let mut rng = Lcg::new(seed);
+    (0..len).map(|_| T::random(&mut rng)).collect()
+}
+
+/// Returns the polynomial with `len` random coefficients from the generator seeded with
+/// `seed`.
+pub(crate) fn random_poly<T: Coefficient + Random>(len: usize, seed: u64) -> Polynomial<T> {
+    Polynomial::new(random_vec(len, seed))
+}
+
+/// Returns the largest magnitude of the difference of matching entries of `a` and `b`.
+///
+/// Panics if the lengths differ.
+pub(crate) fn max_error<T: Coefficient + Magnitude>(a: &[T], b: &[T]) -> f64 {
+    assert_eq!(a.len(), b.len(), "lengths differ");
+    a.iter().zip(b).map(|(x, y)| (x.clone() - y.clone()).magnitude()).fold(0., f64::max)
+}